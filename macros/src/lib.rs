@@ -0,0 +1,206 @@
+//! Procedural macros for `epics` crate, use them through `epics` re-exports.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, format_ident};
+use syn::{
+    parse_macro_input,
+    AttributeArgs, NestedMeta, Meta, Lit,
+    ItemFn, FnArg, Pat, Expr, Attribute,
+};
+
+
+/// Register function as iocsh command.
+///
+/// The command is registered automatically on device support initialization.
+///
+/// + Doc comments of the function become help text of the command (EPICS 7 only).
+/// + `#[iocsh_command(name = "otherName")]` sets the command name,
+///   by default it is the function name.
+/// + `#[default(expr)]` on argument sets its value when the argument is omitted.
+///
+/// Function should return `()` or `epics::Result<T>`.
+/// Returned error is logged and sets iocsh error status (EPICS 7 only).
+///
+/// ```ignore
+/// /// Connect to power supply.
+/// #[epics::iocsh_command(name = "psuConnect")]
+/// fn psu_connect(name: &str, #[default(502)] port: i32) -> epics::Result<()> {
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn iocsh_command(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as AttributeArgs);
+    let mut func = parse_macro_input!(item as ItemFn);
+    match expand(args, &mut func) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(args: AttributeArgs, func: &mut ItemFn) -> syn::Result<TokenStream2> {
+    let fn_name = func.sig.ident.clone();
+    let mut cmd_name = fn_name.to_string();
+    for arg in args.into_iter() {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("name") => {
+                match nv.lit {
+                    Lit::Str(ref s) => cmd_name = s.value(),
+                    ref lit => return Err(syn::Error::new_spanned(lit, "expected string literal")),
+                }
+            },
+            other => return Err(syn::Error::new_spanned(other, "unknown argument, expected `name = \"...\"`")),
+        }
+    }
+    let usage = doc_text(&func.attrs);
+
+    let mut names = Vec::new();
+    let mut loads = Vec::new();
+    let mut defs = Vec::new();
+    for input in func.sig.inputs.iter_mut() {
+        let pt = match input {
+            FnArg::Typed(pt) => pt,
+            FnArg::Receiver(r) => return Err(syn::Error::new_spanned(r, "methods cannot be commands")),
+        };
+        let ident = match *pt.pat {
+            Pat::Ident(ref pi) => pi.ident.clone(),
+            ref p => return Err(syn::Error::new_spanned(p, "expected argument name")),
+        };
+        let default = take_default(&mut pt.attrs)?;
+        let ty = &pt.ty;
+        let arg_name = ident.to_string();
+        match default {
+            // Omitted argument is detected by optional type
+            Some(expr) => {
+                defs.push(quote! {
+                    .arg(#arg_name, <Option<#ty> as ::epics::command::AsType>::dtype())
+                });
+                loads.push(quote! {
+                    let #ident: #ty = <Option<#ty> as ::epics::command::AsType>
+                    ::from_buf(iter.next().unwrap())?
+                    .unwrap_or_else(|| ::std::convert::Into::into(#expr));
+                });
+            },
+            // Value is kept in separate variable, so the argument could borrow it
+            None => {
+                let value = format_ident!("__value_{}", ident);
+                defs.push(quote! {
+                    .arg(#arg_name, <<#ty as ::epics::command::AsArg>::Value as ::epics::command::AsType>::dtype())
+                });
+                loads.push(quote! {
+                    let mut #value = Some(<<#ty as ::epics::command::AsArg>::Value as ::epics::command::AsType>
+                    ::from_buf(iter.next().unwrap())?);
+                    let #ident: #ty = <#ty as ::epics::command::AsArg>::from_value(&mut #value);
+                });
+            },
+        }
+        names.push(ident);
+    }
+    let nargs = names.len();
+
+    Ok(quote! {
+        #func
+
+        const _: () = {
+            extern "C" fn wrapper(args: *const ::epics::epics_sys::iocshArgBuf) {
+                ::epics::command::_run_command(#cmd_name, args, #nargs, |arg_buf| {
+                    #[allow(unused_mut, unused_variables)]
+                    let mut iter = arg_buf.iter();
+                    #( #loads )*
+                    Ok(#fn_name( #( #names ),* ))
+                });
+            }
+            fn register() {
+                ::epics::command::FuncDef::new(#cmd_name)
+                #( #defs )*
+                .usage(#usage)
+                .register(wrapper);
+            }
+            ::epics::inventory::submit! {
+                ::epics::command::CommandEntry::new(register)
+            }
+        };
+    })
+}
+
+/// Join doc comment lines
+fn doc_text(attrs: &[Attribute]) -> String {
+    attrs.iter().filter(|a| a.path.is_ident("doc"))
+    .filter_map(|a| match a.parse_meta() {
+        Ok(Meta::NameValue(nv)) => match nv.lit {
+            Lit::Str(s) => Some(s.value().trim().to_string()),
+            _ => None,
+        },
+        _ => None,
+    })
+    .collect::<Vec<_>>().join("\n")
+}
+
+/// Remove `#[default(expr)]` attribute from argument and return its value
+fn take_default(attrs: &mut Vec<Attribute>) -> syn::Result<Option<Expr>> {
+    match attrs.iter().position(|a| a.path.is_ident("default")) {
+        Some(i) => {
+            let attr = attrs.remove(i);
+            attr.parse_args::<Expr>().map(Some)
+        },
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_str(args: AttributeArgs, mut func: ItemFn) -> (String, ItemFn) {
+        let ts = expand(args, &mut func).unwrap();
+        (ts.to_string(), func)
+    }
+
+    #[test]
+    fn name_and_usage() {
+        let (ts, _) = expand_str(vec![parse_quote!(name = "psuConnect")], parse_quote! {
+            /// Connect to power supply.
+            ///
+            /// Port is optional.
+            fn psu_connect(name: &str) {}
+        });
+        assert!(ts.contains("FuncDef :: new (\"psuConnect\")"));
+        assert!(ts.contains("usage (\"Connect to power supply.\\n\\nPort is optional.\")"));
+    }
+
+    #[test]
+    fn default_name() {
+        let (ts, _) = expand_str(Vec::new(), parse_quote! {
+            fn psu_connect(name: &str) {}
+        });
+        assert!(ts.contains("FuncDef :: new (\"psu_connect\")"));
+        assert!(ts.contains("usage (\"\")"));
+    }
+
+    #[test]
+    fn default_arg() {
+        let (ts, func) = expand_str(Vec::new(), parse_quote! {
+            fn psu_connect(name: &str, #[default(502)] port: i32) {}
+        });
+        // Attribute is removed from the function
+        match func.sig.inputs.iter().nth(1).unwrap() {
+            FnArg::Typed(pt) => assert!(pt.attrs.is_empty()),
+            _ => unreachable!(),
+        }
+        assert!(ts.contains("< Option < i32 > as :: epics :: command :: AsType > :: dtype ()"));
+        assert!(ts.contains("unwrap_or_else (|| :: std :: convert :: Into :: into (502))"));
+    }
+
+    #[test]
+    fn bad_args() {
+        let mut func: ItemFn = parse_quote! { fn f() {} };
+        assert!(expand(vec![parse_quote!(name = 1)], &mut func).is_err());
+        assert!(expand(vec![parse_quote!(title = "f")], &mut func).is_err());
+        let mut method: ItemFn = parse_quote! { fn f(self) {} };
+        assert!(expand(Vec::new(), &mut method).is_err());
+    }
+}
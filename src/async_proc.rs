@@ -1,98 +1,888 @@
 use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::thread::{self, JoinHandle};
-use std::sync::mpsc::{self, Sender, Receiver};
-use std::sync::Mutex;
-use std::sync::atomic::{Ordering, fence};
+use std::sync::mpsc::{self, Sender, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{Ordering, fence, AtomicBool, AtomicU8, AtomicUsize};
+use std::mem;
+use std::time::{Duration, Instant};
 
-use log::{debug, error};
+use log::{debug, warn, error};
 
 use lazy_static::lazy_static;
 
-use crate::record::{AnyReadRecord, AnyWriteRecord};
+use epics_sys::{dbCommon, dbScanLock, dbScanUnlock};
+
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin, task::{Context, Poll, Waker}};
+#[cfg(feature = "async")]
+use futures::executor::{ThreadPool, block_on};
+
+#[cfg(feature = "async")]
+use crate::record::HandlerFuture;
+use crate::record::{
+    Record, AnyReadRecord, AnyWriteRecord,
+    AlarmStatus, AlarmSeverity, Callback,
+    raw_set_alarm,
+};
+use crate::device_support::{
+    raise_read_error_alarm, raise_write_error_alarm,
+    guard, fail_record,
+};
+
+/// Number of record priorities
+pub const PRIORITIES: usize = 3;
+
+/// Record processing priority (PRIO field)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low = 0,
+    Medium,
+    High,
+}
+impl Priority {
+    fn of_record<R: Record + ?Sized>(rec: &R) -> Self {
+        match unsafe { rec.as_raw() }.prio {
+            0 => Priority::Low,
+            1 => Priority::Medium,
+            _ => Priority::High,
+        }
+    }
+}
+
+/// Configuration of asynchronous processing pool
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Number of worker threads for each priority
+    pub threads: [usize; PRIORITIES],
+    /// Names of work queues, each queue has its own thread
+    pub queues: Vec<String>,
+}
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self { threads: [1; PRIORITIES], queues: Vec::new() }
+    }
+}
+
+/// Token that is cancelled when asynchronous request of the record times out
+/// or when processing is stopped.
+///
+/// Handler could poll it in `read_async` or `write_async`
+/// to stop waiting for hung hardware.
+/// Once the token is cancelled the record is owned by EPICS again
+/// and the handler must not access it, see `Record::set_async_timeout`.
+/// Future of request is dropped when its token is cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    flag: Arc<AtomicBool>,
+    #[cfg(feature = "async")]
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::SeqCst)
+    }
+    fn cancel(&self) {
+        self.flag.store(true, Ordering::SeqCst);
+        #[cfg(feature = "async")]
+        {
+            if let Some(waker) = self.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+    /// Wake the task when the token is cancelled
+    #[cfg(feature = "async")]
+    fn register(&self, waker: &Waker) {
+        *self.waker.lock().unwrap() = Some(waker.clone());
+    }
+    #[cfg(feature = "async")]
+    fn same(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.flag, &other.flag)
+    }
+}
+
+const RUNNING: u8 = 0;
+const DONE: u8 = 1;
+const TIMED_OUT: u8 = 2;
+
+/// State of asynchronous request shared between worker and watchdog
+#[derive(Clone)]
+pub(crate) struct Request {
+    state: Arc<AtomicU8>,
+    token: CancelToken,
+}
+impl Request {
+    fn new() -> Self {
+        Self { state: Arc::new(AtomicU8::new(RUNNING)), token: CancelToken::default() }
+    }
+    fn switch(&self, state: u8) -> bool {
+        self.state.compare_exchange(RUNNING, state, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+    /// Returns `true` if the request was completed before timeout
+    fn complete(&self) -> bool {
+        self.switch(DONE)
+    }
+    /// Returns `true` if the request was still running
+    fn expire(&self) -> bool {
+        self.switch(TIMED_OUT)
+    }
+    fn is_running(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == RUNNING
+    }
+}
+
+/// Request watched for timeout
+struct Watch {
+    name: String,
+    deadline: Instant,
+    raw: *mut dbCommon,
+    callback: *mut Callback,
+    request: Request,
+}
+unsafe impl Send for Watch {}
+impl Watch {
+    /// Complete timed out request with TIMEOUT alarm.
+    ///
+    /// Returns the watch back if the record cannot be processed now.
+    unsafe fn expire(self) -> Option<Watch> {
+        if !self.request.expire() {
+            return None;
+        }
+        self.request.token.cancel();
+        error!("record({}): asynchronous request timed out", self.name);
+        raw_set_alarm(&mut *self.raw, AlarmStatus::Timeout, AlarmSeverity::Invalid);
+        fence(Ordering::SeqCst);
+        let watch = self.process()?;
+        warn!("record({}): callback queue is full, processing is retried", watch.name);
+        Some(watch)
+    }
+    /// Request processing of timed out record,
+    /// returns the watch back if callback queue is full.
+    unsafe fn process(self) -> Option<Watch> {
+        match (*self.callback).request() {
+            Ok(()) => None,
+            Err(()) => Some(self),
+        }
+    }
+}
+
+/// Interval of retrying to process timed out records
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
 
 pub(crate) enum Message {
     Break,
-    Read(AnyReadRecord),
-    Write(AnyWriteRecord),
+    Read(AnyReadRecord, Option<Request>),
+    Write(AnyWriteRecord, Option<Request>),
+    #[cfg(feature = "async")]
+    ReadFuture(AnyReadRecord, Guarded, Option<Request>),
+    #[cfg(feature = "async")]
+    WriteFuture(AnyWriteRecord, Guarded, Option<Request>),
 }
 
-struct Handler {
+/// Counters of queue requests
+#[derive(Default)]
+struct QueueStats {
+    pending: AtomicUsize,
+    active: AtomicUsize,
+}
+
+/// Sending side of queue that counts pending requests
+#[derive(Clone)]
+pub(crate) struct QueueSender {
     channel: Sender<Message>,
-    thread: JoinHandle<()>,
+    stats: Arc<QueueStats>,
+}
+impl QueueSender {
+    /// Returns the message back if the queue is stopped
+    fn send(&self, msg: Message) -> Result<(), Message> {
+        self.stats.pending.fetch_add(1, Ordering::SeqCst);
+        self.channel.send(msg).map_err(|e| {
+            self.stats.pending.fetch_sub(1, Ordering::SeqCst);
+            e.0
+        })
+    }
+}
+
+/// Queue of messages served by one or more worker threads
+struct Queue {
+    name: String,
+    sender: QueueSender,
+    receiver: Arc<Mutex<Receiver<Message>>>,
+    threads: Vec<JoinHandle<()>>,
+    done: Receiver<()>,
+}
+impl Queue {
+    fn new(name: &str, nthreads: usize) -> Self {
+        assert!(nthreads > 0);
+        let (tx, rx) = mpsc::channel();
+        let rx = Arc::new(Mutex::new(rx));
+        let (done_tx, done_rx) = mpsc::channel();
+        let stats = Arc::new(QueueStats::default());
+        let threads = (0..nthreads).map(|i| {
+            let rx = rx.clone();
+            let stats = stats.clone();
+            let done = done_tx.clone();
+            thread::Builder::new()
+            .name(format!("{}-{}", name, i))
+            .spawn(move || {
+                handler_loop(rx, stats);
+                let _ = done.send(());
+            })
+            .unwrap()
+        }).collect();
+        Self {
+            name: name.to_string(),
+            sender: QueueSender { channel: tx, stats },
+            receiver: rx,
+            threads,
+            done: done_rx,
+        }
+    }
+    /// Stop worker threads waiting for them until `deadline`.
+    ///
+    /// Threads that are still busy after deadline are detached.
+    fn stop(self, deadline: Instant) {
+        for _ in self.threads.iter() {
+            let _ = self.sender.send(Message::Break);
+        }
+        let mut running = self.threads.len();
+        while running > 0 {
+            let now = Instant::now();
+            let wait = if deadline > now { deadline - now } else { Duration::from_secs(0) };
+            match self.done.recv_timeout(wait) {
+                Ok(()) => running -= 1,
+                Err(_) => break,
+            }
+        }
+        if running > 0 {
+            warn!("{}: {} worker(s) did not stop in time, detached", self.name, running);
+        } else {
+            for thread in self.threads.into_iter() {
+                thread.join().unwrap();
+            }
+        }
+        // Requests left in queue, e.g. behind detached workers
+        while let Ok(msg) = self.receiver.lock().unwrap().try_recv() {
+            unsafe { cancel_message(msg) };
+        }
+        // Detached workers exit when they return
+        for _ in 0..running {
+            let _ = self.sender.send(Message::Break);
+        }
+    }
+    fn info(&self) -> QueueInfo {
+        QueueInfo {
+            name: self.name.clone(),
+            threads: self.threads.len(),
+            pending: self.sender.stats.pending.load(Ordering::SeqCst),
+            active: self.sender.stats.active.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// State of asynchronous processing queue
+#[derive(Debug, Clone)]
+pub struct QueueInfo {
+    pub name: String,
+    pub threads: usize,
+    /// Number of requests waiting in queue
+    pub pending: usize,
+    /// Number of requests being handled
+    pub active: usize,
+}
+
+/// Handle of named work queue that record is bound to
+#[derive(Clone)]
+pub(crate) struct QueueHandle {
+    name: String,
+    sender: QueueSender,
+}
+impl QueueHandle {
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Queues for each priority and named work queues
+struct Pool {
+    queues: Vec<Queue>,
+    named: BTreeMap<String, Queue>,
+    watchdog: (Sender<Option<Watch>>, JoinHandle<()>),
+    /// Runtime of handler futures that are not bound to work queue
+    #[cfg(feature = "async")]
+    runtime: ThreadPool,
 }
 
 lazy_static! {
-    static ref HANDLER: Mutex<Option<Handler>> = Mutex::new(None);
+    static ref POOL: Mutex<Option<Pool>> = Mutex::new(None);
+    static ref STOPPING: AtomicBool = AtomicBool::new(false);
+    /// Held for reading while request is sent and for writing when stopping,
+    /// so no request is sent to queue after it is stopped
+    static ref SENDING: RwLock<()> = RwLock::new(());
 }
 
 thread_local! {
-    static CHANNEL: Cell<Option<Sender<Message>>> = Cell::new(None);
+    static CHANNELS: Cell<Option<Vec<QueueSender>>> = Cell::new(None);
+}
+
+/// Release the record after its handler returned.
+///
+/// Returns `false` if the request has already been completed by watchdog,
+/// in that case the result is dropped and the record is not touched
+/// except for disabling it if the handler panicked.
+unsafe fn complete<R, T>(rec: &mut R, request: Option<Request>, res: &Result<T, String>) -> bool
+where R: Record + ?Sized {
+    let late = match request {
+        Some(req) => !req.complete(),
+        None => false,
+    };
+    if !late {
+        rec.private().set_busy(false);
+        return true;
+    }
+    // Record could be processed concurrently after timeout
+    dbScanLock(rec.as_raw_mut() as *mut _);
+    rec.private_mut().take_convert();
+    rec.private().set_busy(false);
+    match res {
+        Ok(_) => warn!("record({}): asynchronous request returned after timeout", rec.name()),
+        Err(p) => fail_record(rec, format!("asynchronous request panicked after timeout: {}", p)),
+    }
+    dbScanUnlock(rec.as_raw_mut() as *mut _);
+    false
+}
+
+/// Process the record after its read request is handled
+unsafe fn finish_read(mut rec: AnyReadRecord, request: Option<Request>, res: Result<crate::Result<()>, String>, op: &str) {
+    if !complete(&mut *rec, request, &res) {
+        return;
+    }
+    match res {
+        Ok(Ok(())) => debug!("{}({})", op, rec.name()),
+        Ok(Err(e)) => {
+            error!("{}({}): {}", op, rec.name(), e);
+            rec.private_mut().take_convert();
+            raise_read_error_alarm(&mut *rec);
+        },
+        Err(p) => {
+            rec.private_mut().take_convert();
+            // Disabled record is left active and is not processed
+            fail_record(&mut *rec, format!("{}: panic: {}", op, p));
+            return;
+        },
+    }
+    fence(Ordering::SeqCst);
+    rec.process().unwrap();
+}
+
+/// Process the record after its write request is handled
+unsafe fn finish_write(mut rec: AnyWriteRecord, request: Option<Request>, res: Result<crate::Result<()>, String>, op: &str) {
+    if !complete(&mut *rec, request, &res) {
+        return;
+    }
+    rec.private_mut().take_convert();
+    match res {
+        Ok(Ok(())) => debug!("{}({})", op, rec.name()),
+        Ok(Err(e)) => {
+            error!("{}({}): {}", op, rec.name(), e);
+            raise_write_error_alarm(&mut *rec);
+        },
+        Err(p) => {
+            // Disabled record is left active and is not processed
+            fail_record(&mut *rec, format!("{}: panic: {}", op, p));
+            return;
+        },
+    }
+    fence(Ordering::SeqCst);
+    rec.process().unwrap();
+}
+
+fn handle_message(msg: Message) -> bool {
+    match msg {
+        Message::Break => return false,
+        Message::Read(mut rec, request) => unsafe {
+            rec.private().set_busy(true);
+            let res = guard(|| rec.handler_read_async().unwrap_or_else(|| {
+                Err(crate::Error::Other("no handler".into()))
+            }));
+            finish_read(rec, request, res, "record_read_async");
+        },
+        Message::Write(mut rec, request) => unsafe {
+            rec.private().set_busy(true);
+            let res = guard(|| rec.handler_write_async().unwrap_or_else(|| {
+                Err(crate::Error::Other("no handler".into()))
+            }));
+            finish_write(rec, request, res, "record_write_async");
+        },
+        #[cfg(feature = "async")]
+        Message::ReadFuture(rec, fut, request) => unsafe {
+            finish_read_future(rec, request, block_on(fut));
+        },
+        #[cfg(feature = "async")]
+        Message::WriteFuture(rec, fut, request) => unsafe {
+            finish_write_future(rec, request, block_on(fut));
+        },
+    }
+    true
+}
+
+/// Complete request that is dropped on stop with DISABLE alarm
+unsafe fn cancel_request<R: Record + ?Sized>(rec: &mut R, request: Option<Request>) {
+    // Timed out request is already completed by watchdog
+    if let Some(req) = request {
+        if !req.complete() {
+            return;
+        }
+    }
+    warn!("record({}): asynchronous request dropped on stop", rec.name());
+    rec.private_mut().take_convert();
+    rec.set_alarm(AlarmStatus::Disable, AlarmSeverity::Invalid);
+    fence(Ordering::SeqCst);
+    if rec.process().is_err() {
+        error!("record({}): cannot complete dropped request", rec.name());
+    }
 }
 
-fn handler_loop(channel: Receiver<Message>) {
+unsafe fn cancel_message(msg: Message) {
+    match msg {
+        Message::Break => (),
+        Message::Read(mut rec, request) => cancel_request(&mut *rec, request),
+        Message::Write(mut rec, request) => cancel_request(&mut *rec, request),
+        #[cfg(feature = "async")]
+        Message::ReadFuture(mut rec, fut, request) => {
+            drop(fut);
+            rec.private().set_busy(false);
+            cancel_request(&mut *rec, request);
+        },
+        #[cfg(feature = "async")]
+        Message::WriteFuture(mut rec, fut, request) => {
+            drop(fut);
+            rec.private().set_busy(false);
+            cancel_request(&mut *rec, request);
+        },
+    }
+}
+
+fn handler_loop(channel: Arc<Mutex<Receiver<Message>>>, stats: Arc<QueueStats>) {
     loop {
-        match channel.recv().unwrap() {
-            Message::Break => break,
-            Message::Read(mut rec) => unsafe {
-                match rec.handler_read_async().unwrap_or_else(|| {
-                    Err(crate::Error::Other("no handler".into()))
-                }) {
-                    Ok(()) => debug!("record_read_async({})", rec.name()),
-                    Err(e) => error!("record_read_async({}): {}", rec.name(), e),
-                }
-                fence(Ordering::SeqCst);
-                rec.process().unwrap();
-            },
-            Message::Write(mut rec) => unsafe {
-                match rec.handler_write_async().unwrap_or_else(|| {
-                    Err(crate::Error::Other("no handler".into()))
-                }) {
-                    Ok(()) => debug!("record_write_async({})", rec.name()),
-                    Err(e) => error!("record_write_async({}): {}", rec.name(), e),
-                }
-                fence(Ordering::SeqCst);
-                rec.process().unwrap();
+        let msg = match channel.lock().unwrap().recv() {
+            Ok(msg) => msg,
+            // All senders are dropped, same as `Break`
+            Err(_) => break,
+        };
+        stats.pending.fetch_sub(1, Ordering::SeqCst);
+        if STOPPING.load(Ordering::SeqCst) {
+            // Pending requests are cancelled on stop
+            match msg {
+                Message::Break => break,
+                msg => {
+                    unsafe { cancel_message(msg) };
+                    continue;
+                },
+            }
+        }
+        stats.active.fetch_add(1, Ordering::SeqCst);
+        let cont = handle_message(msg);
+        stats.active.fetch_sub(1, Ordering::SeqCst);
+        if !cont {
+            break;
+        }
+    }
+}
+
+fn watchdog_loop(channel: Receiver<Option<Watch>>) {
+    let mut watches = Vec::<Watch>::new();
+    // Timed out records that are not processed yet
+    let mut retries = Vec::<Watch>::new();
+    loop {
+        retries = retries.into_iter().filter_map(|w| unsafe { w.process() }).collect();
+        let now = Instant::now();
+        let (expired, pending): (Vec<_>, Vec<_>) = watches.into_iter()
+        .partition(|w| w.deadline <= now);
+        for watch in expired.into_iter() {
+            retries.extend(unsafe { watch.expire() });
+        }
+        watches = pending.into_iter().filter(|w| w.request.is_running()).collect();
+
+        let retry = if retries.is_empty() { None } else { Some(Instant::now() + RETRY_INTERVAL) };
+        let res = match watches.iter().map(|w| w.deadline).chain(retry).min() {
+            Some(deadline) => {
+                let now = Instant::now();
+                let wait = if deadline > now { deadline - now } else { Duration::from_secs(0) };
+                channel.recv_timeout(wait)
             },
+            None => channel.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match res {
+            Ok(Some(watch)) => watches.push(watch),
+            Ok(None) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => (),
+        }
+    }
+    // Let hung handlers know that nobody waits for them
+    for watch in mem::replace(&mut watches, Vec::new()).into_iter() {
+        watch.request.token.cancel();
+    }
+    for watch in retries.into_iter() {
+        if let Some(watch) = unsafe { watch.process() } {
+            error!("record({}): cannot process timed out record", watch.name);
         }
     }
 }
 
-pub unsafe fn start_loop() {
+pub unsafe fn start_loop(config: PoolConfig) {
+    let queues = config.threads.iter().enumerate().map(|(prio, n)| {
+        Queue::new(&format!("async-proc-{}", prio), *n)
+    }).collect();
+    let named = config.queues.iter().map(|name| {
+        (name.clone(), Queue::new(&format!("queue-{}", name), 1))
+    }).collect();
     let (tx, rx) = mpsc::channel();
-    let jh = thread::spawn(move || handler_loop(rx));
-    let mut guard = HANDLER.lock().unwrap();
+    let watchdog = (tx, thread::Builder::new()
+        .name("async-watchdog".into())
+        .spawn(move || watchdog_loop(rx))
+        .unwrap()
+    );
+    #[cfg(feature = "async")]
+    let runtime = ThreadPool::builder()
+        .name_prefix("async-rt-")
+        .create()
+        .unwrap();
+    let mut guard = POOL.lock().unwrap();
     assert!(guard.is_none());
-    *guard = Some(Handler { channel: tx, thread: jh })
+    *guard = Some(Pool {
+        queues, named, watchdog,
+        #[cfg(feature = "async")]
+        runtime,
+    })
 }
 
+/// Time to wait for requests that are being handled on stop
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Stop processing, requests that are not started yet
+/// and pending futures are completed with DISABLE alarm.
+///
+/// Waits for requests that are being handled for `STOP_TIMEOUT` at most.
 pub unsafe fn stop_loop() {
-    let handler = {
-        let mut guard = HANDLER.lock().unwrap();
-        guard.take().unwrap()
+    {
+        let _sending = SENDING.write().unwrap();
+        STOPPING.store(true, Ordering::SeqCst);
+    }
+    let pool = match POOL.lock().unwrap().take() {
+        Some(pool) => pool,
+        None => return,
     };
-    handler.channel.send(Message::Break).unwrap();
-    handler.thread.join().unwrap()
+    #[cfg(feature = "async")]
+    {
+        for token in FUTURES.lock().unwrap().iter() {
+            token.cancel();
+        }
+    }
+    let (channel, thread) = pool.watchdog;
+    let _ = channel.send(None);
+    thread.join().unwrap();
+    let deadline = Instant::now() + STOP_TIMEOUT;
+    for queue in pool.queues.into_iter() {
+        queue.stop(deadline);
+    }
+    for (_, queue) in pool.named.into_iter() {
+        queue.stop(deadline);
+    }
+    // Runtime threads exit when all spawned futures are dropped
+    #[cfg(feature = "async")]
+    {
+        drop(pool.runtime);
+        while futures_in_flight() > 0 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let n = futures_in_flight();
+        if n > 0 {
+            warn!("{} future(s) did not stop in time", n);
+        }
+    }
 }
 
-fn with_channel<F: FnOnce(&Sender<Message>)>(f: F) {
-    CHANNEL.with(|chan_cell| {
-        let channel = match chan_cell.replace(None) {
-            Some(chan) => chan,
-            None => (*HANDLER.lock().unwrap()).as_ref().unwrap().channel.clone(),
+/// Find named work queue
+pub(crate) fn find_queue(name: &str) -> crate::Result<QueueHandle> {
+    let guard = POOL.lock().unwrap();
+    let pool = guard.as_ref().ok_or("async processing is not started")?;
+    pool.named.get(name).map(|q| QueueHandle {
+        name: name.to_string(),
+        sender: q.sender.clone(),
+    }).ok_or_else(|| format!("no such work queue: '{}'", name).into())
+}
+
+/// State of all queues, empty if processing is not started
+pub fn queues_info() -> Vec<QueueInfo> {
+    let guard = POOL.lock().unwrap();
+    match guard.as_ref() {
+        Some(pool) => {
+            pool.queues.iter().chain(pool.named.values())
+            .map(|q| q.info())
+            .collect()
+        },
+        None => Vec::new(),
+    }
+}
+
+/// Returns `None` if processing is not started or stopped
+fn with_channel<T, F: FnOnce(&QueueSender) -> T>(prio: Priority, f: F) -> Option<T> {
+    CHANNELS.with(|chan_cell| {
+        let channels = match chan_cell.replace(None) {
+            Some(chans) => chans,
+            None => (*POOL.lock().unwrap()).as_ref()?
+                .queues.iter().map(|q| q.sender.clone()).collect(),
         };
-        f(&channel);
-        assert!(chan_cell.replace(Some(channel)).is_none())
-    });
+        let ret = f(&channels[prio as usize]);
+        assert!(chan_cell.replace(Some(channels)).is_none());
+        Some(ret)
+    })
 }
 
-pub unsafe fn record_write(record: AnyWriteRecord) {
-    with_channel(|channel| {
-        channel.send(Message::Write(record)).unwrap();
-    });
+/// Start watching for timeout if it is set for the record
+unsafe fn watch<R: Record + ?Sized>(rec: &mut R) -> Option<Request> {
+    let timeout = rec.async_timeout()?;
+    let request = Request::new();
+    rec.private_mut().set_cancel_token(request.token.clone());
+    let watch = Watch {
+        name: rec.name().to_string(),
+        deadline: Instant::now() + timeout,
+        raw: rec.as_raw_mut() as *mut _,
+        callback: rec.private_mut().callback_ptr(),
+        request: request.clone(),
+    };
+    let guard = POOL.lock().unwrap();
+    guard.as_ref()?.watchdog.0.send(Some(watch)).ok()?;
+    Some(request)
+}
+
+/// Send request to worker, returns it back if processing is stopped
+fn send_message(prio: Priority, queue: Option<QueueHandle>, msg: Message) -> Result<(), Message> {
+    let _sending = SENDING.read().unwrap();
+    if STOPPING.load(Ordering::SeqCst) {
+        return Err(msg);
+    }
+    match queue {
+        Some(queue) => queue.sender.send(msg),
+        None => {
+            let mut msg = Some(msg);
+            match with_channel(prio, |channel| channel.send(msg.take().unwrap())) {
+                Some(res) => res,
+                None => Err(msg.take().unwrap()),
+            }
+        },
+    }
+}
+
+/// Send write request to worker.
+///
+/// Returns the record back if processing is stopped.
+pub unsafe fn record_write(mut record: AnyWriteRecord) -> Result<(), AnyWriteRecord> {
+    let prio = Priority::of_record(&*record);
+    let queue = record.private().queue();
+    let request = watch(&mut *record);
+    send_message(prio, queue, Message::Write(record, request.clone())).map_err(|msg| {
+        if let Some(req) = request {
+            req.complete();
+        }
+        match msg {
+            Message::Write(rec, _) => rec,
+            _ => unreachable!(),
+        }
+    })
 }
 
-pub unsafe fn record_read(record: AnyReadRecord) {
-    with_channel(|channel| {
-        channel.send(Message::Read(record)).unwrap();
+/// Send read request to worker.
+///
+/// Returns the record back if processing is stopped.
+pub unsafe fn record_read(mut record: AnyReadRecord) -> Result<(), AnyReadRecord> {
+    let prio = Priority::of_record(&*record);
+    let queue = record.private().queue();
+    let request = watch(&mut *record);
+    send_message(prio, queue, Message::Read(record, request.clone())).map_err(|msg| {
+        if let Some(req) = request {
+            req.complete();
+        }
+        match msg {
+            Message::Read(rec, _) => rec,
+            _ => unreachable!(),
+        }
+    })
+}
+
+#[cfg(feature = "async")]
+lazy_static! {
+    /// Cancel tokens of handler futures that are not completed yet
+    static ref FUTURES: Mutex<Vec<CancelToken>> = Mutex::new(Vec::new());
+}
+
+/// Number of handler futures that are not completed yet
+#[cfg(feature = "async")]
+pub fn futures_in_flight() -> usize {
+    FUTURES.lock().unwrap().len()
+}
+
+/// Future of request that catches panics of handler future
+/// and is dropped when its cancel token is cancelled.
+///
+/// Resolves to `None` if cancelled.
+#[cfg(feature = "async")]
+pub(crate) struct Guarded {
+    fut: HandlerFuture,
+    token: CancelToken,
+}
+#[cfg(feature = "async")]
+impl Guarded {
+    /// Set cancel token of the record and register the future
+    unsafe fn new<R>(fut: HandlerFuture, rec: &mut R, request: &Option<Request>) -> Self
+    where R: Record + ?Sized {
+        let token = match request {
+            Some(req) => req.token.clone(),
+            None => CancelToken::default(),
+        };
+        rec.private_mut().set_cancel_token(token.clone());
+        FUTURES.lock().unwrap().push(token.clone());
+        if STOPPING.load(Ordering::SeqCst) {
+            token.cancel();
+        }
+        Self { fut, token }
+    }
+}
+#[cfg(feature = "async")]
+impl Drop for Guarded {
+    fn drop(&mut self) {
+        let token = &self.token;
+        FUTURES.lock().unwrap().retain(|t| !t.same(token));
+    }
+}
+#[cfg(feature = "async")]
+impl Future for Guarded {
+    type Output = Option<Result<crate::Result<()>, String>>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(None);
+        }
+        self.token.register(cx.waker());
+        // Token could be cancelled before waker is registered
+        if self.token.is_cancelled() {
+            return Poll::Ready(None);
+        }
+        let fut = &mut self.fut;
+        match guard(|| fut.as_mut().poll(cx)) {
+            Ok(Poll::Ready(res)) => Poll::Ready(Some(Ok(res))),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(p) => Poll::Ready(Some(Err(p))),
+        }
+    }
+}
+
+/// Complete request whose future is cancelled
+#[cfg(feature = "async")]
+unsafe fn finish_cancelled<R: Record + ?Sized>(rec: &mut R, request: Option<Request>) {
+    // Future cancelled by timeout is already completed by watchdog
+    if complete(rec, request, &Ok(())) {
+        cancel_request(rec, None);
+    }
+}
+
+#[cfg(feature = "async")]
+unsafe fn finish_read_future(mut rec: AnyReadRecord, request: Option<Request>, res: Option<Result<crate::Result<()>, String>>) {
+    match res {
+        Some(res) => finish_read(rec, request, res, "record_read_future"),
+        None => finish_cancelled(&mut *rec, request),
+    }
+}
+
+#[cfg(feature = "async")]
+unsafe fn finish_write_future(mut rec: AnyWriteRecord, request: Option<Request>, res: Option<Result<crate::Result<()>, String>>) {
+    match res {
+        Some(res) => finish_write(rec, request, res, "record_write_future"),
+        None => finish_cancelled(&mut *rec, request),
+    }
+}
+
+/// Run future by the runtime, returns the record back if processing is stopped
+#[cfg(feature = "async")]
+fn spawn<T, F>(rec: T, fut: Guarded, f: F) -> Result<(), T>
+where T: Send + 'static, F: FnOnce(T, Option<Result<crate::Result<()>, String>>) + Send + 'static {
+    if STOPPING.load(Ordering::SeqCst) {
+        return Err(rec);
+    }
+    let runtime = match POOL.lock().unwrap().as_ref() {
+        Some(pool) => pool.runtime.clone(),
+        None => return Err(rec),
+    };
+    runtime.spawn_ok(async move {
+        let res = fut.await;
+        f(rec, res);
     });
+    Ok(())
+}
+
+/// Run future of read request and process the record on completion.
+///
+/// Future is run in the work queue the record is bound to, if any.
+/// Returns the record back if processing is stopped.
+#[cfg(feature = "async")]
+pub(crate) unsafe fn spawn_read(mut record: AnyReadRecord, fut: HandlerFuture) -> Result<(), AnyReadRecord> {
+    let prio = Priority::of_record(&*record);
+    let queue = record.private().queue();
+    let request = watch(&mut *record);
+    let fut = Guarded::new(fut, &mut *record, &request);
+    // Future owns the record until it is completed
+    record.private().set_busy(true);
+    let res = match queue {
+        Some(queue) => {
+            send_message(prio, Some(queue), Message::ReadFuture(record, fut, request.clone()))
+            .map_err(|msg| match msg {
+                Message::ReadFuture(rec, _, _) => rec,
+                _ => unreachable!(),
+            })
+        },
+        None => {
+            let req = request.clone();
+            spawn(record, fut, move |rec, res| finish_read_future(rec, req, res))
+        },
+    };
+    res.map_err(|rec| {
+        rec.private().set_busy(false);
+        if let Some(req) = request {
+            req.complete();
+        }
+        rec
+    })
+}
+
+/// Run future of write request and process the record on completion.
+///
+/// See `spawn_read`.
+#[cfg(feature = "async")]
+pub(crate) unsafe fn spawn_write(mut record: AnyWriteRecord, fut: HandlerFuture) -> Result<(), AnyWriteRecord> {
+    let prio = Priority::of_record(&*record);
+    let queue = record.private().queue();
+    let request = watch(&mut *record);
+    let fut = Guarded::new(fut, &mut *record, &request);
+    record.private().set_busy(true);
+    let res = match queue {
+        Some(queue) => {
+            send_message(prio, Some(queue), Message::WriteFuture(record, fut, request.clone()))
+            .map_err(|msg| match msg {
+                Message::WriteFuture(rec, _, _) => rec,
+                _ => unreachable!(),
+            })
+        },
+        None => {
+            let req = request.clone();
+            spawn(record, fut, move |rec, res| finish_write_future(rec, req, res))
+        },
+    };
+    res.map_err(|rec| {
+        rec.private().set_busy(false);
+        if let Some(req) = request {
+            req.complete();
+        }
+        rec
+    })
 }
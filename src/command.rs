@@ -1,10 +1,14 @@
 use std::slice;
+use std::fmt::Debug;
 use std::ffi::{CStr, CString};
 use std::ptr;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::collections::BTreeMap;
 
-use libc::{c_int};
+use libc::{c_int, c_char, c_void};
+
+use log::{debug, error};
 
 use lazy_static::lazy_static;
 
@@ -12,10 +16,67 @@ use epics_sys::{
     self,
     iocshRegister, iocshFuncDef,
     iocshArg, iocshArgType, iocshArgBuf,
+    epicsExit,
 };
+#[cfg(feature = "base7")]
+use epics_sys::iocshSetError;
+
+use crate::device_support;
 
 lazy_static! {
     static ref COMMANDS: Mutex<BTreeMap<CString, FuncDef>> = Mutex::new(BTreeMap::new());
+    /// Names of closure commands by trampoline index
+    static ref SLOTS: Mutex<Vec<CString>> = Mutex::new(Vec::new());
+    static ref ABORT_ON_ERROR: AtomicBool = AtomicBool::new(false);
+    static ref IOC_RUNNING: AtomicBool = AtomicBool::new(false);
+}
+
+
+/// Command implemented by closure
+pub type CommandFn = Box<dyn Fn(&ArgBuf) -> crate::Result<()> + Send + Sync>;
+
+/// Maximum number of commands registered by `FuncDef::register_fn`
+pub const MAX_CLOSURE_COMMANDS: usize = 64;
+
+type RawCommandFn = extern "C" fn(*const iocshArgBuf);
+
+// iocsh does not pass command name or any user data to the function,
+// so each closure command gets its own trampoline.
+macro_rules! trampolines {
+    ( $( $n:expr ),* ) => {
+        [ $( {
+            extern "C" fn trampoline(args: *const iocshArgBuf) {
+                dispatch($n, args)
+            }
+            trampoline as RawCommandFn
+        }, )* ]
+    };
+}
+static TRAMPOLINES: [RawCommandFn; MAX_CLOSURE_COMMANDS] = trampolines!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
+    48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63
+);
+
+fn dispatch(slot: usize, args: *const iocshArgBuf) {
+    // Locks are released before the call, so the closure could register commands
+    let cname = SLOTS.lock().unwrap().get(slot).cloned();
+    let found = cname.as_ref().and_then(|cname| {
+        let commands = COMMANDS.lock().unwrap();
+        let def = commands.get(cname)?;
+        Some((def.args.len(), def.func.clone()?))
+    });
+    let name = match cname {
+        Some(cname) => cname.to_string_lossy().into_owned(),
+        None => format!("<closure {}>", slot),
+    };
+    match found {
+        Some((nargs, func)) => _run_command(&name, args, nargs, |arg_buf| Ok(func(arg_buf))),
+        None => _run_command(&name, args, 0, |_| -> crate::Result<()> {
+            Err(format!("no closure registered for trampoline {}", slot).into())
+        }),
+    }
 }
 
 #[allow(dead_code)]
@@ -24,6 +85,8 @@ pub struct FuncDef {
     raw_args: Vec<*const iocshArg>,
     name: CString,
     args: Vec<ArgDef>,
+    usage: Option<CString>,
+    func: Option<Arc<dyn Fn(&ArgBuf) -> crate::Result<()> + Send + Sync>>,
 }
 impl FuncDef {
     pub fn new(name: &str) -> Self {
@@ -33,15 +96,29 @@ impl FuncDef {
                 name: name.as_c_str().as_ptr(),
                 nargs: 0,
                 arg: ptr::null(),
+                #[cfg(feature = "base7")]
+                usage: ptr::null(),
             }),
             raw_args: Vec::new(),
             name, args: Vec::new(),
+            usage: None,
+            func: None,
         }
     }
     pub fn arg(mut self, name: &str, dtype: ArgType) -> Self {
         self.args.push(ArgDef::new(name, dtype));
         self
     }
+    /// Help text of the command, shown only by EPICS 7 (`base7` feature)
+    pub fn usage(mut self, text: &str) -> Self {
+        let text = CString::new(text.to_string()).unwrap();
+        #[cfg(feature = "base7")]
+        {
+            self.raw.usage = text.as_c_str().as_ptr();
+        }
+        self.usage = Some(text);
+        self
+    }
     pub fn register(mut self, f: extern "C" fn(*const iocshArgBuf)) {
         for arg in self.args.iter() {
             self.raw_args.push(arg.as_raw() as *const _);
@@ -51,6 +128,27 @@ impl FuncDef {
         unsafe { iocshRegister(self.raw.as_ref() as *const _, Some(f)) };
         assert!(COMMANDS.lock().unwrap().insert(self.name.clone(), self).is_none());
     }
+    /// Register closure as command, so it could use shared state of device support.
+    ///
+    /// At most `MAX_CLOSURE_COMMANDS` (64) commands could be registered this way,
+    /// beyond that "too many closure commands" error is returned.
+    /// Also fails if command with the same name already exists.
+    pub fn register_fn(mut self, f: CommandFn) -> crate::Result<()> {
+        if COMMANDS.lock().unwrap().contains_key(&self.name) {
+            return Err(format!("command '{}' already exists", self.name.to_string_lossy()).into());
+        }
+        let slot = {
+            let mut slots = SLOTS.lock().unwrap();
+            if slots.len() >= MAX_CLOSURE_COMMANDS {
+                return Err("too many closure commands".into());
+            }
+            slots.push(self.name.clone());
+            slots.len() - 1
+        };
+        self.func = Some(Arc::from(f));
+        self.register(TRAMPOLINES[slot]);
+        Ok(())
+    }
 }
 unsafe impl Send for FuncDef {}
 
@@ -91,48 +189,277 @@ impl ArgType {
             ArgType::Int              => epics_sys::iocshArgType_iocshArgInt,
             ArgType::Double           => epics_sys::iocshArgType_iocshArgDouble,
             ArgType::String           => epics_sys::iocshArgType_iocshArgString,
-            ArgType::PersistentString => epics_sys::iocshArgType_iocshArgPdbbase,
-            ArgType::Pdbbase          => epics_sys::iocshArgType_iocshArgArgv,
-            ArgType::Argv             => epics_sys::iocshArgType_iocshArgPersistentString,
+            ArgType::PersistentString => epics_sys::iocshArgType_iocshArgPersistentString,
+            ArgType::Pdbbase          => epics_sys::iocshArgType_iocshArgPdbbase,
+            ArgType::Argv             => epics_sys::iocshArgType_iocshArgArgv,
         }
     }
 }
 
-pub trait AsType {
+pub trait AsType: Sized {
     fn dtype() -> ArgType;
-    fn from_buf(buf: &iocshArgBuf) -> Self;
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self>;
+}
+
+unsafe fn str_from_ptr<'a>(ptr: *const c_char) -> crate::Result<Option<&'a str>> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        CStr::from_ptr(ptr).to_str().map(Some)
+        .map_err(|e| format!("argument is not valid UTF-8: {}", e).into())
+    }
 }
 
 impl AsType for i32 {
     fn dtype() -> ArgType {
         ArgType::Int
     }
-    fn from_buf(buf: &iocshArgBuf) -> Self {
-        (unsafe { buf.ival }) as Self
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok((unsafe { buf.ival }) as Self)
     }
 }
 impl AsType for f64 {
     fn dtype() -> ArgType {
         ArgType::Double
     }
-    fn from_buf(buf: &iocshArgBuf) -> Self {
-        (unsafe { buf.dval }) as Self
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok((unsafe { buf.dval }) as Self)
     }
 }
+/// Integer argument, non-zero is true
+impl AsType for bool {
+    fn dtype() -> ArgType {
+        ArgType::Int
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok((unsafe { buf.ival }) != 0)
+    }
+}
+/// Omitted argument is empty string
 impl<'a> AsType for &'a str {
     fn dtype() -> ArgType {
         ArgType::String
     }
-    fn from_buf(buf: &iocshArgBuf) -> Self {
-        unsafe { CStr::from_ptr(buf.sval) }.to_str().unwrap()
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok(unsafe { str_from_ptr(buf.sval) }?.unwrap_or(""))
     }
 }
-impl<'a> AsType for String {
+/// Persistent string, the value is copied and EPICS keeps its own copy
+impl AsType for String {
     fn dtype() -> ArgType {
         ArgType::PersistentString
     }
-    fn from_buf(buf: &iocshArgBuf) -> Self {
-        unsafe { CString::from_raw(buf.sval) }.into_string().unwrap()
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok(String::from(unsafe { str_from_ptr(buf.sval) }?.unwrap_or("")))
+    }
+}
+/// `None` if the argument is omitted
+impl<'a> AsType for Option<&'a str> {
+    fn dtype() -> ArgType {
+        ArgType::String
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        unsafe { str_from_ptr(buf.sval) }
+    }
+}
+/// `None` if the argument is omitted
+impl AsType for Option<String> {
+    fn dtype() -> ArgType {
+        ArgType::PersistentString
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok(unsafe { str_from_ptr(buf.sval) }?.map(String::from))
+    }
+}
+
+// iocsh passes zero for omitted numeric arguments,
+// so optional numbers are passed as strings and parsed.
+macro_rules! impl_as_type_option {
+    ($T:ty) => {
+        /// `None` if the argument is omitted
+        impl AsType for Option<$T> {
+            fn dtype() -> ArgType {
+                ArgType::String
+            }
+            fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+                match unsafe { str_from_ptr(buf.sval) }? {
+                    Some(s) if !s.is_empty() => s.parse().map(Some).map_err(|e| {
+                        format!("cannot parse '{}' as {}: {}", s, stringify!($T), e).into()
+                    }),
+                    _ => Ok(None),
+                }
+            }
+        }
+    };
+}
+impl_as_type_option!(i32);
+impl_as_type_option!(f64);
+
+/// `None` if the argument is omitted, integer as `bool`, non-zero is true
+impl AsType for Option<bool> {
+    fn dtype() -> ArgType {
+        ArgType::String
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok(<Option<i32>>::from_buf(buf)?.map(|x| x != 0))
+    }
+}
+
+/// Remaining arguments of the command, the first one is the command name
+impl<'a> AsType for Vec<&'a str> {
+    fn dtype() -> ArgType {
+        ArgType::Argv
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        let (ac, av) = unsafe { (buf.aval.ac, buf.aval.av) };
+        if av.is_null() {
+            return Ok(Vec::new());
+        }
+        unsafe { slice::from_raw_parts(av, ac as usize) }.iter().enumerate()
+        .map(|(i, p)| {
+            unsafe { str_from_ptr(*p) }?
+            .ok_or_else(|| format!("argv item {} is null", i).into())
+        })
+        .collect()
+    }
+}
+/// Remaining arguments of the command, the first one is the command name
+impl AsType for Vec<String> {
+    fn dtype() -> ArgType {
+        ArgType::Argv
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok(<Vec<&str>>::from_buf(buf)?.into_iter().map(String::from).collect())
+    }
+}
+
+/// Type of command function parameter.
+///
+/// Every `AsType` is passed by value,
+/// remaining arguments could also be borrowed as `&[&str]`.
+pub trait AsArg<'a>: Sized {
+    /// Type the argument is loaded as
+    type Value: AsType;
+    fn from_value(value: &'a mut Option<Self::Value>) -> Self;
+}
+impl<'a, T: AsType> AsArg<'a> for T {
+    type Value = T;
+    fn from_value(value: &'a mut Option<T>) -> Self {
+        value.take().unwrap()
+    }
+}
+/// Remaining arguments of the command, the first one is the command name
+impl<'a, 'b> AsArg<'a> for &'a [&'b str] {
+    type Value = Vec<&'b str>;
+    fn from_value(value: &'a mut Option<Vec<&'b str>>) -> Self {
+        let value: &'a Option<Vec<&'b str>> = value;
+        value.as_ref().unwrap()
+    }
+}
+
+/// Handle of EPICS database (pdbbase)
+#[derive(Debug, Clone, Copy)]
+pub struct DbBase {
+    raw: *mut c_void,
+}
+impl DbBase {
+    pub fn as_raw(&self) -> *mut c_void {
+        self.raw
+    }
+}
+impl AsType for DbBase {
+    fn dtype() -> ArgType {
+        ArgType::Pdbbase
+    }
+    fn from_buf(buf: &iocshArgBuf) -> crate::Result<Self> {
+        Ok(Self { raw: unsafe { buf.vval } })
+    }
+}
+
+/// Value returned by command function
+pub trait CommandResult {
+    /// Debug representation of successful result
+    fn into_result(self) -> crate::Result<String>;
+}
+impl CommandResult for () {
+    fn into_result(self) -> crate::Result<String> {
+        Ok(format!("{:?}", self))
+    }
+}
+impl<T: Debug> CommandResult for crate::Result<T> {
+    fn into_result(self) -> crate::Result<String> {
+        self.map(|t| format!("{:?}", t))
+    }
+}
+
+/// Set iocsh error status, so the script running the command fails.
+///
+/// `iocshSetError` appeared in EPICS 7, older versions have no way
+/// to report command failure to iocsh, so it is only logged there.
+#[cfg(feature = "base7")]
+fn set_error_status() {
+    unsafe { iocshSetError(1) };
+}
+#[cfg(not(feature = "base7"))]
+fn set_error_status() {}
+
+fn command_failed(name: &str) {
+    set_error_status();
+    if ABORT_ON_ERROR.load(Ordering::SeqCst) && !IOC_RUNNING.load(Ordering::SeqCst) {
+        error!("command '{}' failed, aborting startup", name);
+        unsafe { epicsExit(1) };
+    }
+}
+
+pub(crate) fn set_abort_on_error(abort: bool) {
+    ABORT_ON_ERROR.store(abort, Ordering::SeqCst);
+}
+pub(crate) fn set_ioc_running() {
+    IOC_RUNNING.store(true, Ordering::SeqCst);
+}
+
+/// Run command function with arguments passed by iocsh.
+///
+/// Used by generated command wrappers.
+#[doc(hidden)]
+pub fn _run_command<F, R>(name: &str, args: *const iocshArgBuf, nargs: usize, f: F)
+where F: FnOnce(&ArgBuf) -> crate::Result<R>, R: CommandResult {
+    if !device_support::check_gate() {
+        error!("command '{}': device support broken", name);
+        command_failed(name);
+        return;
+    }
+    let arg_buf = ArgBuf::new(args, nargs);
+    // Panic must not unwind into iocsh
+    match device_support::guard(|| f(&arg_buf).and_then(|r| r.into_result())) {
+        Ok(Ok(t)) => debug!("command '{}': {}", name, t),
+        Ok(Err(e)) => {
+            error!("command '{}': {}", name, e);
+            command_failed(name);
+        },
+        Err(p) => {
+            error!("command '{}': panic: {}", name, p);
+            command_failed(name);
+        },
+    }
+}
+
+/// Command registered by `iocsh_command` attribute
+#[doc(hidden)]
+pub struct CommandEntry {
+    register: fn(),
+}
+impl CommandEntry {
+    pub fn new(register: fn()) -> Self {
+        Self { register }
+    }
+}
+inventory::collect!(CommandEntry);
+
+/// Register commands annotated with `iocsh_command` attribute
+pub(crate) fn register_commands() {
+    for entry in inventory::iter::<CommandEntry> {
+        (entry.register)();
     }
 }
 
@@ -146,6 +473,13 @@ impl<'a> ArgBuf<'a> {
     pub fn iter(&self) -> slice::Iter<'a, iocshArgBuf> {
         self.buffer.iter()
     }
+    /// Argument `n` converted to `T`
+    pub fn get<T: AsType>(&self, n: usize) -> crate::Result<T> {
+        match self.buffer.get(n) {
+            Some(buf) => T::from_buf(buf),
+            None => Err(format!("no argument {}", n).into()),
+        }
+    }
 }
 
 #[macro_export]
@@ -153,6 +487,13 @@ macro_rules! _replace {
     ($_t:tt, $sub:expr) => { $sub };
 }
 
+/// Register function as iocsh command.
+///
+/// If the function returns `Err` or its arguments cannot be parsed,
+/// the error is logged and, with `base7` feature, iocsh error status is set,
+/// so `iocshCmd` or script running the command fails.
+/// Without `base7` feature (EPICS 3.15, 3.16) the failure is only logged,
+/// use `Context::set_abort_on_command_error` to stop startup instead.
 #[macro_export]
 macro_rules! register_command {
     (
@@ -173,27 +514,22 @@ macro_rules! register_command {
     ) => {{
         let _: &mut $crate::context::Context = $context;
         extern "C" fn wrapper(args: *const $crate::epics_sys::iocshArgBuf) {
-            if !$crate::device_support::check_gate() {
-                $crate::log::error!("command '{}': device support broken", stringify!($fn_name));
-                return;
-            }
             fn user_func( $( $arg_name : $arg_type ),* ) -> $fn_ret $fn_body
             let len = {<[()]>::len(&[ $( $crate::_replace!($arg_type, ()) ),* ])};
-            let arg_buf = $crate::command::ArgBuf::new(args, len);
-            let mut iter = arg_buf.iter();
-            match user_func($(
-                <$arg_type as $crate::command::AsType>::from_buf(iter.next().unwrap())
-            ),*) {
-                Ok(t) => $crate::log::debug!("command '{}': {:?}", stringify!($fn_name), t),
-                Err(e) => {
-                    let e: $crate::Error = e;
-                    $crate::log::error!("command '{}': {}", stringify!($fn_name), e)
-                },
-            };
+            $crate::command::_run_command(stringify!($fn_name), args, len, |arg_buf| {
+                #[allow(unused_mut, unused_variables)]
+                let mut iter = arg_buf.iter();
+                Ok(user_func($(
+                    <$arg_type as $crate::command::AsArg>::from_value(&mut Some(
+                        <<$arg_type as $crate::command::AsArg>::Value as $crate::command::AsType>
+                        ::from_buf(iter.next().unwrap())?
+                    ))
+                ),*))
+            });
         }
         $crate::command::FuncDef::new(stringify!($fn_name))
         $(
-            .arg(stringify!($arg_name), <$arg_type as $crate::command::AsType>::dtype())
+            .arg(stringify!($arg_name), <<$arg_type as $crate::command::AsArg>::Value as $crate::command::AsType>::dtype())
         )*
         .register(wrapper);
     }};
@@ -1,11 +1,63 @@
 use std::marker::PhantomData;
 
+use crate::record::{AlarmStatus, AlarmSeverity};
+use crate::device_support;
+use crate::command;
+use crate::async_proc::{PoolConfig, Priority};
+
 
 pub struct Context {
     phantom: PhantomData<()>,
+    pub(crate) pool: PoolConfig,
 }
 impl Context {
     pub unsafe fn new() -> Self {
-        Self { phantom: PhantomData }
+        Self { phantom: PhantomData, pool: PoolConfig::default() }
+    }
+
+    /// Set number of threads performing asynchronous requests
+    /// of records with given priority (PRIO field). Default is 1.
+    pub fn set_async_threads(&mut self, prio: Priority, n: usize) -> crate::Result<()> {
+        if n == 0 {
+            return Err("number of async threads should be positive".into());
+        }
+        self.pool.threads[prio as usize] = n;
+        Ok(())
+    }
+
+    /// Create named work queue with its own thread.
+    ///
+    /// Records bound to the queue by `Record::bind_queue`
+    /// are processed one at a time in order of requests.
+    pub fn create_queue(&mut self, name: &str) -> crate::Result<()> {
+        if self.pool.queues.iter().any(|q| q == name) {
+            return Err(format!("work queue '{}' already exists", name).into());
+        }
+        self.pool.queues.push(name.to_string());
+        Ok(())
+    }
+
+    /// Set alarm raised when handler fails to read record.
+    /// `None` means that the error is only logged.
+    ///
+    /// Default is `READ` status with `INVALID` severity.
+    /// The alarm is not raised if handler has already raised one itself.
+    pub fn set_read_error_alarm(&mut self, alarm: Option<(AlarmStatus, AlarmSeverity)>) {
+        device_support::set_read_error_alarm(alarm);
+    }
+    /// Set alarm raised when handler fails to write record.
+    /// `None` means that the error is only logged.
+    ///
+    /// Default is `WRITE` status with `INVALID` severity.
+    /// The alarm is not raised if handler has already raised one itself.
+    pub fn set_write_error_alarm(&mut self, alarm: Option<(AlarmStatus, AlarmSeverity)>) {
+        device_support::set_write_error_alarm(alarm);
+    }
+
+    /// Abort IOC startup (`epicsExit(1)`) when any command fails
+    /// before IOC is running. Failed commands are only logged by default
+    /// and, with `base7` feature, set iocsh error status.
+    pub fn set_abort_on_command_error(&mut self, abort: bool) {
+        command::set_abort_on_error(abort);
     }
 }
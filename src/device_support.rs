@@ -1,12 +1,23 @@
+use std::any::Any;
+use std::cell::Cell;
 use std::panic;
+use std::mem;
+use std::ptr;
 use std::convert::TryFrom;
+use std::ffi::CString;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering, fence};
 
-use log::{debug, error};
+use libc::c_void;
+
+use log::{debug, warn, error};
 
 use lazy_static::lazy_static;
 
-use epics_sys::{IOSCANPVT};
+use epics_sys::{
+    IOSCANPVT, dbScanLock, dbScanUnlock, epicsStdoutPrintf,
+    initHookRegister, initHookState, initHookState_initHookAfterIocRunning,
+};
 
 use crate::record::*;
 use crate::Context;
@@ -16,22 +27,40 @@ use crate::async_proc;
 
 lazy_static! {
     static ref GATE: AtomicBool = AtomicBool::new(true);
+    static ref READ_ERROR_ALARM: Mutex<Option<(AlarmStatus, AlarmSeverity)>> =
+        Mutex::new(Some((AlarmStatus::Read, AlarmSeverity::Invalid)));
+    static ref WRITE_ERROR_ALARM: Mutex<Option<(AlarmStatus, AlarmSeverity)>> =
+        Mutex::new(Some((AlarmStatus::Write, AlarmSeverity::Invalid)));
+    /// Initialized records with functions that create their handlers
+    static ref RECORDS: Mutex<Vec<(AnyRecord, RecordInitFn)>> = Mutex::new(Vec::new());
+}
+
+/// Function that creates handler for record
+pub type RecordInitFn = fn(&mut AnyRecord) -> crate::Result<AnyHandlerBox>;
+
+thread_local! {
+    static GUARDED: Cell<bool> = Cell::new(false);
+}
+
+#[cfg(not(feature = "base7"))]
+use epics_sys::epicsAtExit;
+/// `epicsAtExit` is a macro over `epicsAtExit3` in EPICS 7
+#[cfg(feature = "base7")]
+#[allow(non_snake_case)]
+unsafe fn epicsAtExit(func: epics_sys::epicsExitFunc, arg: *mut c_void) -> libc::c_int {
+    epics_sys::epicsAtExit3(func, arg, b"rsbind_shutdown\0".as_ptr() as *const _)
 }
 
 fn overwrite_panic() {
     let default_hook = panic::take_hook();
 
     panic::set_hook(Box::new(move |panic_info| {
-        GATE.store(false, Ordering::SeqCst);
-        
-        let payload = panic_info.payload();
-        let payload = match payload.downcast_ref::<String>() {
-            Some(payload) => payload.clone(),
-            None => match payload.downcast_ref::<&str>() {
-                Some(payload) => String::from(*payload),
-                None => String::new(),
-            },
-        };
+        // Panic in guarded code disables only the affected record
+        if !GUARDED.with(|g| g.get()) {
+            GATE.store(false, Ordering::SeqCst);
+        }
+
+        let payload = payload_string(panic_info.payload());
         let location = match panic_info.location() {
             Some(location) => format!(" in file '{}' at line {}", location.file(), location.line()),
             None => String::new(),
@@ -42,14 +71,220 @@ fn overwrite_panic() {
     }));
 }
 
+fn payload_string(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<String>() {
+        Some(payload) => payload.clone(),
+        None => match payload.downcast_ref::<&str>() {
+            Some(payload) => String::from(*payload),
+            None => String::new(),
+        },
+    }
+}
+
 pub fn check_gate() -> bool {
     GATE.load(Ordering::SeqCst)
 }
 
+/// Information about record bound to device support
+#[derive(Debug, Clone)]
+pub struct RecordInfo {
+    pub name: String,
+    pub rtype: RecordType,
+    /// Handler name, the first item of INP/OUT link
+    pub handler: String,
+    /// Rest items of INP/OUT link
+    pub args: Vec<String>,
+    /// Name of work queue the record is bound to
+    pub queue: Option<String>,
+    /// Reason why the record is disabled
+    pub failure: Option<String>,
+}
+
+/// Information about all records initialized by device support
+pub fn records_info() -> Vec<RecordInfo> {
+    RECORDS.lock().unwrap().iter_mut().map(|(rec, _)| unsafe {
+        // Failure could be set or cleared by concurrent processing
+        dbScanLock(rec.as_raw_mut() as *mut _);
+        let mut items = rec.link().split(',').map(|s| s.trim().to_string());
+        let info = RecordInfo {
+            name: rec.name().to_string(),
+            rtype: rec.rtype(),
+            handler: items.next().unwrap_or_default(),
+            args: items.collect(),
+            queue: rec.queue_name(),
+            failure: rec.failure().map(String::from),
+        };
+        dbScanUnlock(rec.as_raw_mut() as *mut _);
+        info
+    }).collect()
+}
+
+/// Print lines to IOC shell output, unlike `println!` it follows iocsh redirection
+fn print_lines(lines: Vec<String>) {
+    for line in lines.into_iter() {
+        let line = CString::new(line.replace('\0', "")).unwrap();
+        unsafe { epicsStdoutPrintf(b"%s\n\0".as_ptr() as *const _, line.as_ptr()) };
+    }
+}
+
+/// Report of records in `dbior` style, details are shown if `level > 0`
+pub(crate) fn records_report(records: &[RecordInfo], level: i32) -> Vec<String> {
+    let mut lines = vec![format!("Device Support: rsbind, records: {}", records.len())];
+    for info in records.iter() {
+        lines.push(format!("    {} {:?} handler: {}", info.name, info.rtype, info.handler));
+        if level > 0 {
+            lines.push(format!("        args: {}", info.args.join(", ")));
+            if let Some(ref queue) = info.queue {
+                lines.push(format!("        queue: {}", queue));
+            }
+            if let Some(ref failure) = info.failure {
+                lines.push(format!("        disabled: {}", failure));
+            }
+        }
+    }
+    lines
+}
+
+/// Report of asynchronous processing queues in `dbior` style
+pub(crate) fn queues_report(queues: &[async_proc::QueueInfo], futures: Option<usize>) -> Vec<String> {
+    let mut lines = vec![format!("Device Support: rsbind, queues: {}", queues.len())];
+    for info in queues.iter() {
+        lines.push(format!(
+            "    {} threads: {} pending: {} active: {}",
+            info.name, info.threads, info.pending, info.active,
+        ));
+    }
+    if let Some(n) = futures {
+        lines.push(format!("    futures: {}", n));
+    }
+    lines
+}
+
+/// Report of device support state in `dbior` style
+pub(crate) fn status_report(gate: bool, records: &[RecordInfo]) -> Vec<String> {
+    let failed = records.iter().filter(|r| r.failure.is_some()).collect::<Vec<_>>();
+    let mut lines = vec![
+        format!("Device Support: rsbind, gate: {}", if gate { "open" } else { "closed" }),
+        format!("    records: {} disabled: {}", records.len(), failed.len()),
+    ];
+    for info in failed.into_iter() {
+        lines.push(format!("    {} disabled: {}", info.name, info.failure.as_ref().unwrap()));
+    }
+    lines
+}
+
+fn report_records(level: i32) {
+    print_lines(records_report(&records_info(), level));
+}
+
+fn report_queues() {
+    #[cfg(feature = "async")]
+    let futures = Some(async_proc::futures_in_flight());
+    #[cfg(not(feature = "async"))]
+    let futures = None;
+    print_lines(queues_report(&async_proc::queues_info(), futures));
+}
+
+fn report_status() {
+    print_lines(status_report(check_gate(), &records_info()));
+}
+
+/// Run `f` catching panic.
+///
+/// Panic inside `f` does not close the gate, its message is returned instead.
+pub(crate) fn guard<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    let prev = GUARDED.with(|g| g.replace(true));
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    GUARDED.with(|g| g.set(prev));
+    res.map_err(|payload| payload_string(&*payload))
+}
+
+/// Disable the record after its handler failed.
+///
+/// PACT is left set so that EPICS does not process the record anymore,
+/// DISABLE/INVALID alarm is applied immediately.
+pub(crate) fn fail_record<R: Record + ?Sized>(rec: &mut R, msg: String) {
+    error!("record({}) is disabled: {}", rec.name(), msg);
+    unsafe {
+        rec.set_pact(true);
+        let raw = rec.as_raw_mut();
+        raw.stat = AlarmStatus::Disable.as_raw() as _;
+        raw.sevr = AlarmSeverity::Invalid.as_raw() as _;
+    }
+    rec.set_alarm(AlarmStatus::Disable, AlarmSeverity::Invalid);
+    unsafe { rec.private_mut() }.set_failure(msg);
+}
+/// Make disabled record processable again, alarm is reset on next processing
+unsafe fn enable_record<R: Record + ?Sized>(rec: &mut R) {
+    let raw = rec.as_raw_mut();
+    raw.nsta = AlarmStatus::No.as_raw() as _;
+    raw.nsev = AlarmSeverity::No.as_raw() as _;
+    rec.set_pact(false);
+}
+/// Raise alarm if the record is disabled.
+///
+/// Returns `true` if it is.
+fn check_failed<R: Record + ?Sized>(rec: &mut R) -> bool {
+    if rec.failure().is_some() {
+        rec.set_alarm(AlarmStatus::Disable, AlarmSeverity::Invalid);
+        true
+    } else {
+        false
+    }
+}
+
+/// Raise alarm if the handler has not returned from timed out request yet.
+///
+/// Returns `true` if it has not.
+fn check_busy<R: Record + ?Sized>(rec: &mut R) -> bool {
+    if unsafe { rec.private() }.is_busy() {
+        debug!("record({}): handler is busy with timed out request", rec.name());
+        rec.set_alarm(AlarmStatus::Timeout, AlarmSeverity::Invalid);
+        true
+    } else {
+        false
+    }
+}
+
+pub(crate) fn set_read_error_alarm(alarm: Option<(AlarmStatus, AlarmSeverity)>) {
+    *READ_ERROR_ALARM.lock().unwrap() = alarm;
+}
+pub(crate) fn set_write_error_alarm(alarm: Option<(AlarmStatus, AlarmSeverity)>) {
+    *WRITE_ERROR_ALARM.lock().unwrap() = alarm;
+}
+
+fn raise_error_alarm<R>(rec: &mut R, alarm: &Mutex<Option<(AlarmStatus, AlarmSeverity)>>)
+where R: Record + ?Sized {
+    // Alarm raised by handler itself takes precedence
+    if rec.nsev().map_or(true, |sevr| sevr == AlarmSeverity::No) {
+        if let Some((stat, sevr)) = *alarm.lock().unwrap() {
+            rec.set_alarm(stat, sevr);
+        }
+    }
+}
+pub(crate) fn raise_read_error_alarm<R: Record + ?Sized>(rec: &mut R) {
+    raise_error_alarm(rec, &READ_ERROR_ALARM);
+}
+pub(crate) fn raise_write_error_alarm<R: Record + ?Sized>(rec: &mut R) {
+    raise_error_alarm(rec, &WRITE_ERROR_ALARM);
+}
+
 pub unsafe fn init<F>(f: F) where F: Fn(&mut Context) -> crate::Result<()> {
     overwrite_panic();
-    async_proc::start_loop();
     let mut ctx = Context::new();
+    crate::command::register_commands();
+    crate::register_command!(&mut ctx, fn rsbind_restart(name: &str) -> crate::Result<()> {
+        crate::device_support::restart_record(name)
+    });
+    crate::register_command!(&mut ctx, fn rsbind_records(level: i32) {
+        crate::device_support::report_records(level)
+    });
+    crate::register_command!(&mut ctx, fn rsbind_queues() {
+        crate::device_support::report_queues()
+    });
+    crate::register_command!(&mut ctx, fn rsbind_status() {
+        crate::device_support::report_status()
+    });
     match f(&mut ctx) {
         Ok(()) => {
             debug!("init");
@@ -59,80 +294,233 @@ pub unsafe fn init<F>(f: F) where F: Fn(&mut Context) -> crate::Result<()> {
             GATE.store(false, Ordering::SeqCst);
         },
     }
+    async_proc::start_loop(ctx.pool.clone());
+    if epicsAtExit(Some(shutdown), ptr::null_mut()) != 0 {
+        error!("init: cannot register exit hook");
+    }
+    if initHookRegister(Some(init_hook)) != 0 {
+        error!("init: cannot register init hook");
+    }
+}
+
+unsafe extern "C" fn init_hook(state: initHookState) {
+    if state == initHookState_initHookAfterIocRunning {
+        crate::command::set_ioc_running();
+    }
 }
 
-pub unsafe fn record_init<R, F>(raw: R::Raw, f: F, ret: i32) -> i32 where
-R: Record + FromRaw + Into<AnyRecord>, 
-F: Fn(&mut AnyRecord) -> crate::Result<AnyHandlerBox> {
-    let mut rec = R::from_raw(raw).into();
+unsafe extern "C" fn shutdown(_arg: *mut c_void) {
+    debug!("shutdown");
+    async_proc::stop_loop();
+    let records = mem::replace(&mut *RECORDS.lock().unwrap(), Vec::new());
+    for (mut rec, _) in records.into_iter() {
+        // Handler of hung request is held by detached worker
+        if rec.private().is_busy() {
+            warn!("shutdown({}): handler is busy", rec.name());
+            continue;
+        }
+        match guard(|| rec.handler_shutdown()) {
+            Ok(None) if rec.failure().is_none() => error!("shutdown({}): no handler", rec.name()),
+            Err(p) => error!("shutdown({}): panic: {}", rec.name(), p),
+            _ => (),
+        }
+    }
+}
+
+pub unsafe fn record_init<R>(raw: R::Raw, f: RecordInitFn, ret: i32) -> i32
+where R: Record + FromRaw + Into<AnyRecord> {
+    let rec = R::from_raw(raw);
+    let entry: AnyRecord = rec.duplicate().into();
+    let mut rec: AnyRecord = rec.into();
     rec.init();
+    RECORDS.lock().unwrap().push((entry, f));
     //let mut ctx = Context::new();
-    match f(&mut rec).and_then(|hdl| {
+    match guard(|| f(&mut rec).and_then(|hdl| {
         rec.try_set_handler(hdl)
-    }){
-        Ok(()) => {
+    })) {
+        Ok(Ok(())) => {
             debug!("record_init({})", rec.name());
-            ret
+            if rec.private_mut().take_convert() { 0 } else { ret }
         },
-        Err(e) => {
-            panic!("record_init({}): {}", rec.name(), e);
-            //1
+        Ok(Err(e)) => {
+            fail_record(&mut *rec, format!("record_init: {}", e));
+            1
+        },
+        Err(p) => {
+            fail_record(&mut *rec, format!("record_init: panic: {}", p));
+            1
         },
     }
 }
 
+/// Create handler of the record again and clear its failure state.
+///
+/// Previous handler, if any, is shut down and dropped.
+/// Fails if the record is being processed asynchronously.
+/// If the new handler cannot be created, the record is disabled.
+pub fn restart_record(name: &str) -> crate::Result<()> {
+    let mut records = RECORDS.lock().unwrap();
+    let (rec, init) = records.iter_mut().find(|(r, _)| r.name() == name)
+    .ok_or_else(|| format!("no such record: '{}'", name))?;
+    unsafe {
+        dbScanLock(rec.as_raw_mut() as *mut _);
+        let res = restart_locked(rec, *init);
+        dbScanUnlock(rec.as_raw_mut() as *mut _);
+        res
+    }
+}
+unsafe fn restart_locked(rec: &mut AnyRecord, init: RecordInitFn) -> crate::Result<()> {
+    // Disabled record is left active, otherwise it is being processed.
+    // Handler could be checked out by worker even after timeout.
+    if (rec.pact() && rec.failure().is_none()) || rec.private().is_busy() {
+        return Err(format!("record({}) is busy", rec.name()).into());
+    }
+    let res = restart_handler(rec, init);
+    if let Err(ref e) = res {
+        fail_record(&mut **rec, format!("restart_record: {}", e));
+    }
+    res
+}
+unsafe fn restart_handler(rec: &mut AnyRecord, init: RecordInitFn) -> crate::Result<()> {
+    // Old handler is dropped even if the new one cannot be created
+    let _ = guard(|| rec.handler_shutdown());
+    drop(rec.take_handler());
+    match guard(|| init(rec).and_then(|hdl| rec.try_set_handler(hdl))) {
+        Ok(res) => res?,
+        Err(p) => return Err(format!("panic: {}", p).into()),
+    }
+    if let Some(msg) = rec.private_mut().clear_failure() {
+        debug!("restart_record({}): cleared failure: {}", rec.name(), msg);
+        enable_record(&mut **rec);
+    }
+    match guard(|| rec.handler_reset_scan()) {
+        Ok(res) => res.unwrap_or_else(|| {
+            Err(crate::Error::Other("no handler".into()))
+        })?,
+        Err(p) => return Err(format!("panic: {}", p).into()),
+    }
+    debug!("restart_record({})", rec.name());
+    Ok(())
+}
+
 pub unsafe fn record_set_scan<R>(
     detach: bool, raw: R::Raw, ppvt: *mut IOSCANPVT
 ) -> i32 where R: ScanRecord + FromRaw {
     let mut rec = R::from_raw(raw);
     if detach {
-        panic!(
-            "record '{}' was deleted from I/O event list: {}",
-            rec.name(), "this action is not supported yet",
-        );
+        return record_unset_scan(&mut rec, ppvt);
     }
-    let scan = rec.create_scan();
+    // Scan handle is created once, EPICS keeps the list for the record
+    let scan = rec.attach_scan();
     *ppvt = *scan.as_raw();
-    rec.set_scan(scan.clone());
+    if check_failed(&mut rec) {
+        return 1;
+    }
     //let mut ctx = Context::new();
-    match rec.handler_set_scan(scan).unwrap_or_else(|| {
-        Err(crate::Error::Other("no handler".into()))
-    }) {
-        Ok(()) => {
-            debug!("record_set_scan({})", rec.name());
-            0
+    match guard(|| rec.handler_set_scan(scan)) {
+        Ok(res) => match res.unwrap_or_else(|| {
+            Err(crate::Error::Other("no handler".into()))
+        }) {
+            Ok(()) => {
+                debug!("record_set_scan({})", rec.name());
+                0
+            },
+            Err(e) => {
+                error!("record_set_scan({}): {}", rec.name(), e);
+                1
+            },
         },
-        Err(e) => {
-            error!("record_set_scan({}): {}", rec.name(), e);
+        Err(p) => {
+            fail_record(&mut rec, format!("record_set_scan: panic: {}", p));
             1
         },
     }
 }
+unsafe fn record_unset_scan<R>(rec: &mut R, ppvt: *mut IOSCANPVT) -> i32
+where R: ScanRecord {
+    let scan = match rec.unset_scan() {
+        Some(scan) => scan,
+        None => {
+            error!("record_unset_scan({}): record has no scan", rec.name());
+            return 1;
+        },
+    };
+    // EPICS removes record from the scan list we return here
+    *ppvt = *scan.as_raw();
+    if check_failed(rec) {
+        return 0;
+    }
+    match guard(|| rec.handler_unset_scan()) {
+        Ok(res) => match res.unwrap_or_else(|| {
+            Err(crate::Error::Other("no handler".into()))
+        }) {
+            Ok(()) => debug!("record_unset_scan({})", rec.name()),
+            Err(e) => error!("record_unset_scan({}): {}", rec.name(), e),
+        },
+        Err(p) => fail_record(rec, format!("record_unset_scan: panic: {}", p)),
+    }
+    // Record must be removed from the list anyway
+    0
+}
+
 pub unsafe fn record_read<R>(raw: R::Raw, ret: i32) -> i32
 where R: ReadRecord + FromRaw + Into<AnyReadRecord> {
     let mut rec = R::from_raw(raw);
     if !rec.pact() {
+        if check_failed(&mut rec) {
+            return 1;
+        }
+        if check_busy(&mut rec) {
+            return 1;
+        }
+        #[cfg(feature = "async")]
+        {
+            if let Some(res) = record_read_future(&mut rec, ret) {
+                return res;
+            }
+        }
         //let mut ctx = Context::new();
-        match rec.handler_read().unwrap_or_else(|| {
-            Err(crate::Error::Other("no handler".into()))
-        }) {
-            Ok(a) => {
-                debug!("record_read({})", rec.name());
-                if !a {
-                    rec.set_pact(true);
-                    fence(Ordering::SeqCst);
-                    async_proc::record_read(rec.into());
-                }
-                ret
+        match guard(|| rec.handler_read()) {
+            Ok(res) => match res.unwrap_or_else(|| {
+                Err(crate::Error::Other("no handler".into()))
+            }) {
+                Ok(a) => {
+                    debug!("record_read({})", rec.name());
+                    if !a {
+                        rec.set_pact(true);
+                        fence(Ordering::SeqCst);
+                        match async_proc::record_read(rec.into()) {
+                            Ok(()) => ret,
+                            Err(mut rec) => {
+                                error!("record_read({}): asynchronous processing is stopped", rec.name());
+                                rec.private_mut().take_convert();
+                                rec.set_pact(false);
+                                raise_read_error_alarm(&mut *rec);
+                                1
+                            },
+                        }
+                    } else if rec.private_mut().take_convert() {
+                        0
+                    } else {
+                        ret
+                    }
+                },
+                Err(e) => {
+                    error!("record_read({}): {}", rec.name(), e);
+                    rec.private_mut().take_convert();
+                    raise_read_error_alarm(&mut rec);
+                    1
+                },
             },
-            Err(e) => {
-                error!("record_read({}): {}", rec.name(), e);
+            Err(p) => {
+                rec.private_mut().take_convert();
+                fail_record(&mut rec, format!("record_read: panic: {}", p));
                 1
             },
         }
     } else {
         fence(Ordering::SeqCst);
-        ret
+        if rec.private_mut().take_convert() { 0 } else { ret }
     }
 }
 
@@ -140,21 +528,48 @@ pub unsafe fn record_write<R>(raw: R::Raw) -> i32
 where R: WriteRecord + FromRaw + Into<AnyWriteRecord> {
     let mut rec = R::from_raw(raw);
     if !rec.pact() {
+        if check_failed(&mut rec) {
+            return 1;
+        }
+        if check_busy(&mut rec) {
+            return 1;
+        }
+        #[cfg(feature = "async")]
+        {
+            if let Some(res) = record_write_future(&mut rec) {
+                return res;
+            }
+        }
         //let mut ctx = Context::new();
-        match rec.handler_write().unwrap_or_else(|| {
-            Err(crate::Error::Other("no handler".into()))
-        }) {
-            Ok(a) => {
-                debug!("record_write({})", rec.name());
-                if !a {
-                    rec.set_pact(true);
-                    fence(Ordering::SeqCst);
-                    async_proc::record_write(rec.into());
-                }
-                0
+        let res = guard(|| rec.handler_write());
+        // Raw value is converted by the record only on initialization
+        rec.private_mut().take_convert();
+        match res {
+            Ok(res) => match res.unwrap_or_else(|| {
+                Err(crate::Error::Other("no handler".into()))
+            }) {
+                Ok(a) => {
+                    debug!("record_write({})", rec.name());
+                    if !a {
+                        rec.set_pact(true);
+                        fence(Ordering::SeqCst);
+                        if let Err(mut rec) = async_proc::record_write(rec.into()) {
+                            error!("record_write({}): asynchronous processing is stopped", rec.name());
+                            rec.set_pact(false);
+                            raise_write_error_alarm(&mut *rec);
+                            return 1;
+                        }
+                    }
+                    0
+                },
+                Err(e) => {
+                    error!("record_write({}): {}", rec.name(), e);
+                    raise_write_error_alarm(&mut rec);
+                    1
+                },
             },
-            Err(e) => {
-                error!("record_write({}): {}", rec.name(), e);
+            Err(p) => {
+                fail_record(&mut rec, format!("record_write: panic: {}", p));
                 1
             },
         }
@@ -164,13 +579,105 @@ where R: WriteRecord + FromRaw + Into<AnyWriteRecord> {
     }
 }
 
-pub unsafe fn record_linconv<R>(raw: R::Raw, _after: i32) -> i32
-where R: Record + FromRaw {
-    let rec = R::from_raw(raw);
-    panic!(
-        "record '{}' unexpectedly requested linconv: {}",
-        rec.name(), "this action is not supported yet",
-    );
+/// Start read future if the record has asynchronous handler,
+/// returns `None` otherwise.
+#[cfg(feature = "async")]
+unsafe fn record_read_future<R>(rec: &mut R, ret: i32) -> Option<i32>
+where R: ReadRecord + Into<AnyReadRecord> {
+    let res = match guard(|| rec.handler_read_future()) {
+        Ok(res) => res?,
+        Err(p) => {
+            rec.private_mut().take_convert();
+            fail_record(rec, format!("record_read_future: panic: {}", p));
+            return Some(1);
+        },
+    };
+    Some(match res {
+        Ok(fut) => {
+            debug!("record_read_future({})", rec.name());
+            rec.set_pact(true);
+            fence(Ordering::SeqCst);
+            match async_proc::spawn_read(rec.duplicate().into(), fut) {
+                Ok(()) => ret,
+                Err(mut rec) => {
+                    error!("record_read_future({}): asynchronous processing is stopped", rec.name());
+                    rec.private_mut().take_convert();
+                    rec.set_pact(false);
+                    raise_read_error_alarm(&mut *rec);
+                    1
+                },
+            }
+        },
+        Err(e) => {
+            error!("record_read_future({}): {}", rec.name(), e);
+            rec.private_mut().take_convert();
+            raise_read_error_alarm(rec);
+            1
+        },
+    })
+}
+
+/// Start write future if the record has asynchronous handler,
+/// returns `None` otherwise.
+#[cfg(feature = "async")]
+unsafe fn record_write_future<R>(rec: &mut R) -> Option<i32>
+where R: WriteRecord + Into<AnyWriteRecord> {
+    let res = guard(|| rec.handler_write_future());
+    rec.private_mut().take_convert();
+    let res = match res {
+        Ok(res) => res?,
+        Err(p) => {
+            fail_record(rec, format!("record_write_future: panic: {}", p));
+            return Some(1);
+        },
+    };
+    Some(match res {
+        Ok(fut) => {
+            debug!("record_write_future({})", rec.name());
+            rec.set_pact(true);
+            fence(Ordering::SeqCst);
+            match async_proc::spawn_write(rec.duplicate().into(), fut) {
+                Ok(()) => 0,
+                Err(mut rec) => {
+                    error!("record_write_future({}): asynchronous processing is stopped", rec.name());
+                    rec.set_pact(false);
+                    raise_write_error_alarm(&mut *rec);
+                    1
+                },
+            }
+        },
+        Err(e) => {
+            error!("record_write_future({}): {}", rec.name(), e);
+            raise_write_error_alarm(rec);
+            1
+        },
+    })
+}
+
+pub unsafe fn record_linconv<R>(raw: R::Raw, after: i32) -> i32
+where R: LinconvRecord + FromRaw {
+    let mut rec = R::from_raw(raw);
+    if check_failed(&mut rec) {
+        return 1;
+    }
+    match guard(|| rec.handler_linconv(after != 0)) {
+        Ok(res) => match res.unwrap_or_else(|| {
+            Err(crate::Error::Other("no handler".into()))
+        }) {
+            Ok(()) => {
+                debug!("record_linconv({}, {})", rec.name(), after != 0);
+                0
+            },
+            Err(e) => {
+                error!("record_linconv({}): {}", rec.name(), e);
+                1
+            },
+        },
+        Err(p) => {
+            fail_record(&mut rec, format!("record_linconv: panic: {}", p));
+            1
+        },
+    }
 }
 
 #[macro_export]
@@ -185,7 +692,7 @@ macro_rules! _bind_record_init {
         ) -> $crate::libc::c_long {
             if $crate::device_support::check_gate() {
                 unsafe {
-                    $crate::device_support::record_init::<$crate::record::$rec, _>
+                    $crate::device_support::record_init::<$crate::record::$rec>
                     (rec, $init, $ret) as $crate::libc::c_long
                 }
             } else {
@@ -368,5 +875,80 @@ macro_rules! bind_device_support {
         $crate::_bind_record_init!($record_init, stringoutRecord, StringoutRecord, rsbind_stringout_init_record);
         $crate::_bind_record_set_scan!(stringoutRecord, StringoutRecord, rsbind_stringout_get_ioint_info);
         $crate::_bind_record_write!(stringoutRecord, StringoutRecord, rsbind_stringout_write_stringout);
+
+        // mbbi record
+        $crate::_bind_record_init!($record_init, mbbiRecord, MbbiRecord, rsbind_mbbi_init_record);
+        $crate::_bind_record_set_scan!(mbbiRecord, MbbiRecord, rsbind_mbbi_get_ioint_info);
+        $crate::_bind_record_read!(mbbiRecord, MbbiRecord, rsbind_mbbi_read_mbbi, 2);
+
+        // mbbo record
+        $crate::_bind_record_init!($record_init, mbboRecord, MbboRecord, rsbind_mbbo_init_record, 2);
+        $crate::_bind_record_set_scan!(mbboRecord, MbboRecord, rsbind_mbbo_get_ioint_info);
+        $crate::_bind_record_write!(mbboRecord, MbboRecord, rsbind_mbbo_write_mbbo);
+
+        // mbbiDirect record
+        $crate::_bind_record_init!($record_init, mbbiDirectRecord, MbbiDirectRecord, rsbind_mbbiDirect_init_record);
+        $crate::_bind_record_set_scan!(mbbiDirectRecord, MbbiDirectRecord, rsbind_mbbiDirect_get_ioint_info);
+        $crate::_bind_record_read!(mbbiDirectRecord, MbbiDirectRecord, rsbind_mbbiDirect_read_mbbiDirect, 2);
+
+        // mbboDirect record
+        $crate::_bind_record_init!($record_init, mbboDirectRecord, MbboDirectRecord, rsbind_mbboDirect_init_record, 2);
+        $crate::_bind_record_set_scan!(mbboDirectRecord, MbboDirectRecord, rsbind_mbboDirect_get_ioint_info);
+        $crate::_bind_record_write!(mbboDirectRecord, MbboDirectRecord, rsbind_mbboDirect_write_mbboDirect);
+
+        // waveform record
+        $crate::_bind_record_init!($record_init, waveformRecord, WaveformRecord, rsbind_waveform_init_record);
+        $crate::_bind_record_set_scan!(waveformRecord, WaveformRecord, rsbind_waveform_get_ioint_info);
+        $crate::_bind_record_read!(waveformRecord, WaveformRecord, rsbind_waveform_read_waveform);
+
+        // aai record
+        $crate::_bind_record_init!($record_init, aaiRecord, AaiRecord, rsbind_aai_init_record);
+        $crate::_bind_record_set_scan!(aaiRecord, AaiRecord, rsbind_aai_get_ioint_info);
+        $crate::_bind_record_read!(aaiRecord, AaiRecord, rsbind_aai_read_aai);
+
+        // aao record
+        $crate::_bind_record_init!($record_init, aaoRecord, AaoRecord, rsbind_aao_init_record);
+        $crate::_bind_record_set_scan!(aaoRecord, AaoRecord, rsbind_aao_get_ioint_info);
+        $crate::_bind_record_write!(aaoRecord, AaoRecord, rsbind_aao_write_aao);
+
+        // lsi record
+        $crate::_bind_record_init!($record_init, lsiRecord, LsiRecord, rsbind_lsi_init_record);
+        $crate::_bind_record_set_scan!(lsiRecord, LsiRecord, rsbind_lsi_get_ioint_info);
+        $crate::_bind_record_read!(lsiRecord, LsiRecord, rsbind_lsi_read_lsi);
+
+        // lso record
+        $crate::_bind_record_init!($record_init, lsoRecord, LsoRecord, rsbind_lso_init_record);
+        $crate::_bind_record_set_scan!(lsoRecord, LsoRecord, rsbind_lso_get_ioint_info);
+        $crate::_bind_record_write!(lsoRecord, LsoRecord, rsbind_lso_write_lso);
+
+        // int64in record
+        $crate::_bind_record_init!($record_init, int64inRecord, Int64inRecord, rsbind_int64in_init_record);
+        $crate::_bind_record_set_scan!(int64inRecord, Int64inRecord, rsbind_int64in_get_ioint_info);
+        $crate::_bind_record_read!(int64inRecord, Int64inRecord, rsbind_int64in_read_int64in);
+
+        // int64out record
+        $crate::_bind_record_init!($record_init, int64outRecord, Int64outRecord, rsbind_int64out_init_record);
+        $crate::_bind_record_set_scan!(int64outRecord, Int64outRecord, rsbind_int64out_get_ioint_info);
+        $crate::_bind_record_write!(int64outRecord, Int64outRecord, rsbind_int64out_write_int64out);
+
+        // event record
+        $crate::_bind_record_init!($record_init, eventRecord, EventRecord, rsbind_event_init_record);
+        $crate::_bind_record_set_scan!(eventRecord, EventRecord, rsbind_event_get_ioint_info);
+        $crate::_bind_record_read!(eventRecord, EventRecord, rsbind_event_read_event);
+
+        // calcout record
+        $crate::_bind_record_init!($record_init, calcoutRecord, CalcoutRecord, rsbind_calcout_init_record);
+        $crate::_bind_record_set_scan!(calcoutRecord, CalcoutRecord, rsbind_calcout_get_ioint_info);
+        $crate::_bind_record_write!(calcoutRecord, CalcoutRecord, rsbind_calcout_write_calcout);
+
+        // histogram record
+        $crate::_bind_record_init!($record_init, histogramRecord, HistogramRecord, rsbind_histogram_init_record);
+        $crate::_bind_record_set_scan!(histogramRecord, HistogramRecord, rsbind_histogram_get_ioint_info);
+        $crate::_bind_record_read!(histogramRecord, HistogramRecord, rsbind_histogram_read_histogram);
+
+        // subArray record
+        $crate::_bind_record_init!($record_init, subArrayRecord, SubArrayRecord, rsbind_subArray_init_record);
+        $crate::_bind_record_set_scan!(subArrayRecord, SubArrayRecord, rsbind_subArray_get_ioint_info);
+        $crate::_bind_record_read!(subArrayRecord, SubArrayRecord, rsbind_subArray_read_subArray);
     };
 }
@@ -1,9 +1,16 @@
+extern crate self as epics;
+
 pub use log;
 pub use libc;
 pub use epics_sys;
+#[doc(hidden)]
+pub use inventory;
+
+pub use epics_macros::iocsh_command;
 
 
 pub mod util;
+pub mod time;
 
 pub mod record;
 pub mod context;
@@ -0,0 +1,79 @@
+/// Alarm severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlarmSeverity {
+    No = 0,
+    Minor,
+    Major,
+    Invalid,
+}
+impl AlarmSeverity {
+    pub fn from_raw(raw: u16) -> Option<Self> {
+        [
+            AlarmSeverity::No,
+            AlarmSeverity::Minor,
+            AlarmSeverity::Major,
+            AlarmSeverity::Invalid,
+        ].get(raw as usize).cloned()
+    }
+    pub fn as_raw(self) -> u16 {
+        self as u16
+    }
+}
+
+/// Alarm status (condition)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmStatus {
+    No = 0,
+    Read,
+    Write,
+    HiHi,
+    High,
+    LoLo,
+    Low,
+    State,
+    Cos,
+    Comm,
+    Timeout,
+    HwLimit,
+    Calc,
+    Scan,
+    Link,
+    Soft,
+    BadSub,
+    Udf,
+    Disable,
+    Simm,
+    ReadAccess,
+    WriteAccess,
+}
+impl AlarmStatus {
+    pub fn from_raw(raw: u16) -> Option<Self> {
+        [
+            AlarmStatus::No,
+            AlarmStatus::Read,
+            AlarmStatus::Write,
+            AlarmStatus::HiHi,
+            AlarmStatus::High,
+            AlarmStatus::LoLo,
+            AlarmStatus::Low,
+            AlarmStatus::State,
+            AlarmStatus::Cos,
+            AlarmStatus::Comm,
+            AlarmStatus::Timeout,
+            AlarmStatus::HwLimit,
+            AlarmStatus::Calc,
+            AlarmStatus::Scan,
+            AlarmStatus::Link,
+            AlarmStatus::Soft,
+            AlarmStatus::BadSub,
+            AlarmStatus::Udf,
+            AlarmStatus::Disable,
+            AlarmStatus::Simm,
+            AlarmStatus::ReadAccess,
+            AlarmStatus::WriteAccess,
+        ].get(raw as usize).cloned()
+    }
+    pub fn as_raw(self) -> u16 {
+        self as u16
+    }
+}
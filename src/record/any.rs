@@ -2,11 +2,30 @@ use std::convert::{TryFrom};
 use std::ops::{Deref, DerefMut};
 
 use super::{
-    Record, ReadRecord, WriteRecord,
+    Record, ScanRecord, ReadRecord, WriteRecord,
     AiRecord, AoRecord, AiHandler, AoHandler,
     BiRecord, BoRecord, BiHandler, BoHandler,
     LonginRecord, LongoutRecord, LonginHandler, LongoutHandler,
     StringinRecord, StringoutRecord, StringinHandler, StringoutHandler,
+    MbbiRecord, MbboRecord, MbbiHandler, MbboHandler,
+    MbbiDirectRecord, MbboDirectRecord, MbbiDirectHandler, MbboDirectHandler,
+    WaveformRecord, WaveformHandler,
+    AaiRecord, AaoRecord, AaiHandler, AaoHandler,
+    LsiRecord, LsoRecord, LsiHandler, LsoHandler,
+    Int64inRecord, Int64outRecord, Int64inHandler, Int64outHandler,
+    EventRecord, EventHandler,
+    CalcoutRecord, CalcoutHandler,
+    HistogramRecord, HistogramHandler,
+    SubArrayRecord, SubArrayHandler,
+};
+#[cfg(feature = "async")]
+use super::{
+    AsyncAiHandler, AsyncAoHandler, AsyncBiHandler, AsyncBoHandler,
+    AsyncLonginHandler, AsyncLongoutHandler, AsyncStringinHandler, AsyncStringoutHandler,
+    AsyncMbbiHandler, AsyncMbboHandler, AsyncMbbiDirectHandler, AsyncMbboDirectHandler,
+    AsyncWaveformHandler, AsyncAaiHandler, AsyncAaoHandler, AsyncLsiHandler,
+    AsyncLsoHandler, AsyncInt64inHandler, AsyncInt64outHandler, AsyncEventHandler,
+    AsyncCalcoutHandler, AsyncHistogramHandler, AsyncSubArrayHandler,
 };
 
 /// Record type
@@ -20,6 +39,21 @@ pub enum RecordType {
     Longout,
     Stringin,
     Stringout,
+    Mbbi,
+    Mbbo,
+    MbbiDirect,
+    MbboDirect,
+    Waveform,
+    Aai,
+    Aao,
+    Lsi,
+    Lso,
+    Int64in,
+    Int64out,
+    Event,
+    Calcout,
+    Histogram,
+    SubArray,
 }
 
 macro_rules! into_any {
@@ -58,16 +92,47 @@ macro_rules! try_from_any {
 }
 
 macro_rules! try_set_handler {
-    ($any:ident, $rec:ident, $Handler:ident) => {
-        Box::<dyn $Handler + Send>::try_from($any)
-        .map_err(|_| 1).and_then(|hdl| {
-            match $rec.replace_handler(hdl) {
-                Some(_) => Err(2),
-                None => Ok(()),
+    ($any:ident, $rec:ident, $Handler:ident, $AsyncHandler:ident, $replace:expr) => {
+        if !$replace && $rec.has_handler() {
+            Err(2)
+        } else {
+            match Box::<dyn $Handler + Send>::try_from($any) {
+                Ok(hdl) => {
+                    drop($rec.take_any_handler());
+                    $rec.replace_handler(hdl);
+                    Ok(())
+                },
+                Err(any) => try_set_async_handler!(any, $rec, $AsyncHandler),
             }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+macro_rules! try_set_async_handler {
+    ($any:ident, $rec:ident, $AsyncHandler:ident) => {
+        Box::<dyn $AsyncHandler + Send>::try_from($any)
+        .map_err(|_| 1).map(|hdl| {
+            drop($rec.take_any_handler());
+            $rec.replace_async_handler(hdl);
         })
     };
 }
+#[cfg(not(feature = "async"))]
+macro_rules! try_set_async_handler {
+    ($any:ident, $rec:ident, $AsyncHandler:ident) => {
+        { let _ = $any; Err(1) }
+    };
+}
+
+macro_rules! reset_scan {
+    ($rec:ident) => {
+        match $rec.get_scan() {
+            Some(scan) => $rec.handler_set_scan(scan),
+            None => Some(Ok(())),
+        }
+    };
+}
 
 /// Any record wrapper - could contain any record
 pub enum AnyRecord {
@@ -79,6 +144,21 @@ pub enum AnyRecord {
     Longout(LongoutRecord),
     Stringin(StringinRecord),
     Stringout(StringoutRecord),
+    Mbbi(MbbiRecord),
+    Mbbo(MbboRecord),
+    MbbiDirect(MbbiDirectRecord),
+    MbboDirect(MbboDirectRecord),
+    Waveform(WaveformRecord),
+    Aai(AaiRecord),
+    Aao(AaoRecord),
+    Lsi(LsiRecord),
+    Lso(LsoRecord),
+    Int64in(Int64inRecord),
+    Int64out(Int64outRecord),
+    Event(EventRecord),
+    Calcout(CalcoutRecord),
+    Histogram(HistogramRecord),
+    SubArray(SubArrayRecord),
 }
 impl AnyRecord {
     pub fn rtype(&self) -> RecordType {
@@ -91,20 +171,59 @@ impl AnyRecord {
             AnyRecord::Longout(_) => RecordType::Longout,
             AnyRecord::Stringin(_) => RecordType::Stringin,
             AnyRecord::Stringout(_) => RecordType::Stringout,
+            AnyRecord::Mbbi(_) => RecordType::Mbbi,
+            AnyRecord::Mbbo(_) => RecordType::Mbbo,
+            AnyRecord::MbbiDirect(_) => RecordType::MbbiDirect,
+            AnyRecord::MbboDirect(_) => RecordType::MbboDirect,
+            AnyRecord::Waveform(_) => RecordType::Waveform,
+            AnyRecord::Aai(_) => RecordType::Aai,
+            AnyRecord::Aao(_) => RecordType::Aao,
+            AnyRecord::Lsi(_) => RecordType::Lsi,
+            AnyRecord::Lso(_) => RecordType::Lso,
+            AnyRecord::Int64in(_) => RecordType::Int64in,
+            AnyRecord::Int64out(_) => RecordType::Int64out,
+            AnyRecord::Event(_) => RecordType::Event,
+            AnyRecord::Calcout(_) => RecordType::Calcout,
+            AnyRecord::Histogram(_) => RecordType::Histogram,
+            AnyRecord::SubArray(_) => RecordType::SubArray,
         }
     }
     pub unsafe fn try_set_handler(&mut self, any: AnyHandlerBox)
+    -> Result<(), crate::Error> {
+        self.set_any_handler(any, false)
+    }
+    /// Set handler dropping the previous one if any
+    pub unsafe fn try_replace_handler(&mut self, any: AnyHandlerBox)
+    -> Result<(), crate::Error> {
+        self.set_any_handler(any, true)
+    }
+    unsafe fn set_any_handler(&mut self, any: AnyHandlerBox, replace: bool)
     -> Result<(), crate::Error> {
         let any_type = any.rtype();
         match self {
-            AnyRecord::Ai(ref mut rec) => try_set_handler!(any, rec, AiHandler),
-            AnyRecord::Ao(ref mut rec) => try_set_handler!(any, rec, AoHandler),
-            AnyRecord::Bi(ref mut rec) => try_set_handler!(any, rec, BiHandler),
-            AnyRecord::Bo(ref mut rec) => try_set_handler!(any, rec, BoHandler),
-            AnyRecord::Longin(ref mut rec) => try_set_handler!(any, rec, LonginHandler),
-            AnyRecord::Longout(ref mut rec) => try_set_handler!(any, rec, LongoutHandler),
-            AnyRecord::Stringin(ref mut rec) => try_set_handler!(any, rec, StringinHandler),
-            AnyRecord::Stringout(ref mut rec) => try_set_handler!(any, rec, StringoutHandler),
+            AnyRecord::Ai(ref mut rec) => try_set_handler!(any, rec, AiHandler, AsyncAiHandler, replace),
+            AnyRecord::Ao(ref mut rec) => try_set_handler!(any, rec, AoHandler, AsyncAoHandler, replace),
+            AnyRecord::Bi(ref mut rec) => try_set_handler!(any, rec, BiHandler, AsyncBiHandler, replace),
+            AnyRecord::Bo(ref mut rec) => try_set_handler!(any, rec, BoHandler, AsyncBoHandler, replace),
+            AnyRecord::Longin(ref mut rec) => try_set_handler!(any, rec, LonginHandler, AsyncLonginHandler, replace),
+            AnyRecord::Longout(ref mut rec) => try_set_handler!(any, rec, LongoutHandler, AsyncLongoutHandler, replace),
+            AnyRecord::Stringin(ref mut rec) => try_set_handler!(any, rec, StringinHandler, AsyncStringinHandler, replace),
+            AnyRecord::Stringout(ref mut rec) => try_set_handler!(any, rec, StringoutHandler, AsyncStringoutHandler, replace),
+            AnyRecord::Mbbi(ref mut rec) => try_set_handler!(any, rec, MbbiHandler, AsyncMbbiHandler, replace),
+            AnyRecord::Mbbo(ref mut rec) => try_set_handler!(any, rec, MbboHandler, AsyncMbboHandler, replace),
+            AnyRecord::MbbiDirect(ref mut rec) => try_set_handler!(any, rec, MbbiDirectHandler, AsyncMbbiDirectHandler, replace),
+            AnyRecord::MbboDirect(ref mut rec) => try_set_handler!(any, rec, MbboDirectHandler, AsyncMbboDirectHandler, replace),
+            AnyRecord::Waveform(ref mut rec) => try_set_handler!(any, rec, WaveformHandler, AsyncWaveformHandler, replace),
+            AnyRecord::Aai(ref mut rec) => try_set_handler!(any, rec, AaiHandler, AsyncAaiHandler, replace),
+            AnyRecord::Aao(ref mut rec) => try_set_handler!(any, rec, AaoHandler, AsyncAaoHandler, replace),
+            AnyRecord::Lsi(ref mut rec) => try_set_handler!(any, rec, LsiHandler, AsyncLsiHandler, replace),
+            AnyRecord::Lso(ref mut rec) => try_set_handler!(any, rec, LsoHandler, AsyncLsoHandler, replace),
+            AnyRecord::Int64in(ref mut rec) => try_set_handler!(any, rec, Int64inHandler, AsyncInt64inHandler, replace),
+            AnyRecord::Int64out(ref mut rec) => try_set_handler!(any, rec, Int64outHandler, AsyncInt64outHandler, replace),
+            AnyRecord::Event(ref mut rec) => try_set_handler!(any, rec, EventHandler, AsyncEventHandler, replace),
+            AnyRecord::Calcout(ref mut rec) => try_set_handler!(any, rec, CalcoutHandler, AsyncCalcoutHandler, replace),
+            AnyRecord::Histogram(ref mut rec) => try_set_handler!(any, rec, HistogramHandler, AsyncHistogramHandler, replace),
+            AnyRecord::SubArray(ref mut rec) => try_set_handler!(any, rec, SubArrayHandler, AsyncSubArrayHandler, replace),
         }.map_err(|n| {
             match n {
                 1 => crate::Error::Other(format!(
@@ -116,6 +235,63 @@ impl AnyRecord {
             }
         })
     }
+    /// Remove handler from the record and return it
+    pub unsafe fn take_handler(&mut self) -> Option<AnyHandlerBox> {
+        match self {
+            AnyRecord::Ai(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Ao(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Bi(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Bo(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Longin(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Longout(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Stringin(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Stringout(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Mbbi(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Mbbo(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::MbbiDirect(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::MbboDirect(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Waveform(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Aai(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Aao(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Lsi(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Lso(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Int64in(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Int64out(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Event(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Calcout(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::Histogram(ref mut rec) => rec.take_any_handler(),
+            AnyRecord::SubArray(ref mut rec) => rec.take_any_handler(),
+        }
+    }
+    /// Pass stored `I/O Intr` scan handle to the handler again,
+    /// e.g. after the handler is replaced.
+    pub unsafe fn handler_reset_scan(&mut self) -> Option<crate::Result<()>> {
+        match self {
+            AnyRecord::Ai(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Ao(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Bi(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Bo(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Longin(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Longout(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Stringin(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Stringout(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Mbbi(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Mbbo(ref mut rec) => reset_scan!(rec),
+            AnyRecord::MbbiDirect(ref mut rec) => reset_scan!(rec),
+            AnyRecord::MbboDirect(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Waveform(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Aai(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Aao(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Lsi(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Lso(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Int64in(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Int64out(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Event(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Calcout(ref mut rec) => reset_scan!(rec),
+            AnyRecord::Histogram(ref mut rec) => reset_scan!(rec),
+            AnyRecord::SubArray(ref mut rec) => reset_scan!(rec),
+        }
+    }
 }
 impl Deref for AnyRecord {
     type Target = Record;
@@ -129,6 +305,21 @@ impl Deref for AnyRecord {
             AnyRecord::Longout(ref r) => r,
             AnyRecord::Stringin(ref r) => r,
             AnyRecord::Stringout(ref r) => r,
+            AnyRecord::Mbbi(ref r) => r,
+            AnyRecord::Mbbo(ref r) => r,
+            AnyRecord::MbbiDirect(ref r) => r,
+            AnyRecord::MbboDirect(ref r) => r,
+            AnyRecord::Waveform(ref r) => r,
+            AnyRecord::Aai(ref r) => r,
+            AnyRecord::Aao(ref r) => r,
+            AnyRecord::Lsi(ref r) => r,
+            AnyRecord::Lso(ref r) => r,
+            AnyRecord::Int64in(ref r) => r,
+            AnyRecord::Int64out(ref r) => r,
+            AnyRecord::Event(ref r) => r,
+            AnyRecord::Calcout(ref r) => r,
+            AnyRecord::Histogram(ref r) => r,
+            AnyRecord::SubArray(ref r) => r,
         }
     }
 }
@@ -143,6 +334,21 @@ impl DerefMut for AnyRecord {
             AnyRecord::Longout(ref mut r) => r,
             AnyRecord::Stringin(ref mut r) => r,
             AnyRecord::Stringout(ref mut r) => r,
+            AnyRecord::Mbbi(ref mut r) => r,
+            AnyRecord::Mbbo(ref mut r) => r,
+            AnyRecord::MbbiDirect(ref mut r) => r,
+            AnyRecord::MbboDirect(ref mut r) => r,
+            AnyRecord::Waveform(ref mut r) => r,
+            AnyRecord::Aai(ref mut r) => r,
+            AnyRecord::Aao(ref mut r) => r,
+            AnyRecord::Lsi(ref mut r) => r,
+            AnyRecord::Lso(ref mut r) => r,
+            AnyRecord::Int64in(ref mut r) => r,
+            AnyRecord::Int64out(ref mut r) => r,
+            AnyRecord::Event(ref mut r) => r,
+            AnyRecord::Calcout(ref mut r) => r,
+            AnyRecord::Histogram(ref mut r) => r,
+            AnyRecord::SubArray(ref mut r) => r,
         }
     }
 }
@@ -155,6 +361,21 @@ into_any!(AnyRecord, Longin, LonginRecord);
 into_any!(AnyRecord, Longout, LongoutRecord);
 into_any!(AnyRecord, Stringin, StringinRecord);
 into_any!(AnyRecord, Stringout, StringoutRecord);
+into_any!(AnyRecord, Mbbi, MbbiRecord);
+into_any!(AnyRecord, Mbbo, MbboRecord);
+into_any!(AnyRecord, MbbiDirect, MbbiDirectRecord);
+into_any!(AnyRecord, MbboDirect, MbboDirectRecord);
+into_any!(AnyRecord, Waveform, WaveformRecord);
+into_any!(AnyRecord, Aai, AaiRecord);
+into_any!(AnyRecord, Aao, AaoRecord);
+into_any!(AnyRecord, Lsi, LsiRecord);
+into_any!(AnyRecord, Lso, LsoRecord);
+into_any!(AnyRecord, Int64in, Int64inRecord);
+into_any!(AnyRecord, Int64out, Int64outRecord);
+into_any!(AnyRecord, Event, EventRecord);
+into_any!(AnyRecord, Calcout, CalcoutRecord);
+into_any!(AnyRecord, Histogram, HistogramRecord);
+into_any!(AnyRecord, SubArray, SubArrayRecord);
 
 try_from_any!(AnyRecord, Ai, AiRecord);
 try_from_any!(AnyRecord, Ao, AoRecord);
@@ -164,6 +385,21 @@ try_from_any!(AnyRecord, Longin, LonginRecord);
 try_from_any!(AnyRecord, Longout, LongoutRecord);
 try_from_any!(AnyRecord, Stringin, StringinRecord);
 try_from_any!(AnyRecord, Stringout, StringoutRecord);
+try_from_any!(AnyRecord, Mbbi, MbbiRecord);
+try_from_any!(AnyRecord, Mbbo, MbboRecord);
+try_from_any!(AnyRecord, MbbiDirect, MbbiDirectRecord);
+try_from_any!(AnyRecord, MbboDirect, MbboDirectRecord);
+try_from_any!(AnyRecord, Waveform, WaveformRecord);
+try_from_any!(AnyRecord, Aai, AaiRecord);
+try_from_any!(AnyRecord, Aao, AaoRecord);
+try_from_any!(AnyRecord, Lsi, LsiRecord);
+try_from_any!(AnyRecord, Lso, LsoRecord);
+try_from_any!(AnyRecord, Int64in, Int64inRecord);
+try_from_any!(AnyRecord, Int64out, Int64outRecord);
+try_from_any!(AnyRecord, Event, EventRecord);
+try_from_any!(AnyRecord, Calcout, CalcoutRecord);
+try_from_any!(AnyRecord, Histogram, HistogramRecord);
+try_from_any!(AnyRecord, SubArray, SubArrayRecord);
 
 
 /// Any readable record wrapper
@@ -172,6 +408,15 @@ pub enum AnyReadRecord {
     Bi(BiRecord),
     Longin(LonginRecord),
     Stringin(StringinRecord),
+    Mbbi(MbbiRecord),
+    MbbiDirect(MbbiDirectRecord),
+    Waveform(WaveformRecord),
+    Aai(AaiRecord),
+    Lsi(LsiRecord),
+    Int64in(Int64inRecord),
+    Event(EventRecord),
+    Histogram(HistogramRecord),
+    SubArray(SubArrayRecord),
 }
 impl AnyReadRecord {
     pub fn rtype(&self) -> RecordType {
@@ -180,6 +425,15 @@ impl AnyReadRecord {
             AnyReadRecord::Bi(_) => RecordType::Bi,
             AnyReadRecord::Longin(_) => RecordType::Longin,
             AnyReadRecord::Stringin(_) => RecordType::Stringin,
+            AnyReadRecord::Mbbi(_) => RecordType::Mbbi,
+            AnyReadRecord::MbbiDirect(_) => RecordType::MbbiDirect,
+            AnyReadRecord::Waveform(_) => RecordType::Waveform,
+            AnyReadRecord::Aai(_) => RecordType::Aai,
+            AnyReadRecord::Lsi(_) => RecordType::Lsi,
+            AnyReadRecord::Int64in(_) => RecordType::Int64in,
+            AnyReadRecord::Event(_) => RecordType::Event,
+            AnyReadRecord::Histogram(_) => RecordType::Histogram,
+            AnyReadRecord::SubArray(_) => RecordType::SubArray,
         }
     }
 }
@@ -191,6 +445,15 @@ impl Deref for AnyReadRecord {
             AnyReadRecord::Bi(ref r) => r,
             AnyReadRecord::Longin(ref r) => r,
             AnyReadRecord::Stringin(ref r) => r,
+            AnyReadRecord::Mbbi(ref r) => r,
+            AnyReadRecord::MbbiDirect(ref r) => r,
+            AnyReadRecord::Waveform(ref r) => r,
+            AnyReadRecord::Aai(ref r) => r,
+            AnyReadRecord::Lsi(ref r) => r,
+            AnyReadRecord::Int64in(ref r) => r,
+            AnyReadRecord::Event(ref r) => r,
+            AnyReadRecord::Histogram(ref r) => r,
+            AnyReadRecord::SubArray(ref r) => r,
         }
     }
 }
@@ -201,6 +464,15 @@ impl DerefMut for AnyReadRecord {
             AnyReadRecord::Bi(ref mut r) => r,
             AnyReadRecord::Longin(ref mut r) => r,
             AnyReadRecord::Stringin(ref mut r) => r,
+            AnyReadRecord::Mbbi(ref mut r) => r,
+            AnyReadRecord::MbbiDirect(ref mut r) => r,
+            AnyReadRecord::Waveform(ref mut r) => r,
+            AnyReadRecord::Aai(ref mut r) => r,
+            AnyReadRecord::Lsi(ref mut r) => r,
+            AnyReadRecord::Int64in(ref mut r) => r,
+            AnyReadRecord::Event(ref mut r) => r,
+            AnyReadRecord::Histogram(ref mut r) => r,
+            AnyReadRecord::SubArray(ref mut r) => r,
         }
     }
 }
@@ -209,11 +481,29 @@ into_any!(AnyReadRecord, Ai, AiRecord);
 into_any!(AnyReadRecord, Bi, BiRecord);
 into_any!(AnyReadRecord, Longin, LonginRecord);
 into_any!(AnyReadRecord, Stringin, StringinRecord);
+into_any!(AnyReadRecord, Mbbi, MbbiRecord);
+into_any!(AnyReadRecord, MbbiDirect, MbbiDirectRecord);
+into_any!(AnyReadRecord, Waveform, WaveformRecord);
+into_any!(AnyReadRecord, Aai, AaiRecord);
+into_any!(AnyReadRecord, Lsi, LsiRecord);
+into_any!(AnyReadRecord, Int64in, Int64inRecord);
+into_any!(AnyReadRecord, Event, EventRecord);
+into_any!(AnyReadRecord, Histogram, HistogramRecord);
+into_any!(AnyReadRecord, SubArray, SubArrayRecord);
 
 try_from_any!(AnyReadRecord, Ai, AiRecord);
 try_from_any!(AnyReadRecord, Bi, BiRecord);
 try_from_any!(AnyReadRecord, Longin, LonginRecord);
 try_from_any!(AnyReadRecord, Stringin, StringinRecord);
+try_from_any!(AnyReadRecord, Mbbi, MbbiRecord);
+try_from_any!(AnyReadRecord, MbbiDirect, MbbiDirectRecord);
+try_from_any!(AnyReadRecord, Waveform, WaveformRecord);
+try_from_any!(AnyReadRecord, Aai, AaiRecord);
+try_from_any!(AnyReadRecord, Lsi, LsiRecord);
+try_from_any!(AnyReadRecord, Int64in, Int64inRecord);
+try_from_any!(AnyReadRecord, Event, EventRecord);
+try_from_any!(AnyReadRecord, Histogram, HistogramRecord);
+try_from_any!(AnyReadRecord, SubArray, SubArrayRecord);
 
 
 /// Any writable record wrapper
@@ -222,6 +512,12 @@ pub enum AnyWriteRecord {
     Bo(BoRecord),
     Longout(LongoutRecord),
     Stringout(StringoutRecord),
+    Mbbo(MbboRecord),
+    MbboDirect(MbboDirectRecord),
+    Aao(AaoRecord),
+    Lso(LsoRecord),
+    Int64out(Int64outRecord),
+    Calcout(CalcoutRecord),
 }
 impl AnyWriteRecord {
     pub fn rtype(&self) -> RecordType {
@@ -230,6 +526,12 @@ impl AnyWriteRecord {
             AnyWriteRecord::Bo(_) => RecordType::Bo,
             AnyWriteRecord::Longout(_) => RecordType::Longout,
             AnyWriteRecord::Stringout(_) => RecordType::Stringout,
+            AnyWriteRecord::Mbbo(_) => RecordType::Mbbo,
+            AnyWriteRecord::MbboDirect(_) => RecordType::MbboDirect,
+            AnyWriteRecord::Aao(_) => RecordType::Aao,
+            AnyWriteRecord::Lso(_) => RecordType::Lso,
+            AnyWriteRecord::Int64out(_) => RecordType::Int64out,
+            AnyWriteRecord::Calcout(_) => RecordType::Calcout,
         }
     }
 }
@@ -241,6 +543,12 @@ impl Deref for AnyWriteRecord {
             AnyWriteRecord::Bo(ref r) => r,
             AnyWriteRecord::Longout(ref r) => r,
             AnyWriteRecord::Stringout(ref r) => r,
+            AnyWriteRecord::Mbbo(ref r) => r,
+            AnyWriteRecord::MbboDirect(ref r) => r,
+            AnyWriteRecord::Aao(ref r) => r,
+            AnyWriteRecord::Lso(ref r) => r,
+            AnyWriteRecord::Int64out(ref r) => r,
+            AnyWriteRecord::Calcout(ref r) => r,
         }
     }
 }
@@ -251,6 +559,12 @@ impl DerefMut for AnyWriteRecord {
             AnyWriteRecord::Bo(ref mut r) => r,
             AnyWriteRecord::Longout(ref mut r) => r,
             AnyWriteRecord::Stringout(ref mut r) => r,
+            AnyWriteRecord::Mbbo(ref mut r) => r,
+            AnyWriteRecord::MbboDirect(ref mut r) => r,
+            AnyWriteRecord::Aao(ref mut r) => r,
+            AnyWriteRecord::Lso(ref mut r) => r,
+            AnyWriteRecord::Int64out(ref mut r) => r,
+            AnyWriteRecord::Calcout(ref mut r) => r,
         }
     }
 }
@@ -259,11 +573,23 @@ into_any!(AnyWriteRecord, Ao, AoRecord);
 into_any!(AnyWriteRecord, Bo, BoRecord);
 into_any!(AnyWriteRecord, Longout, LongoutRecord);
 into_any!(AnyWriteRecord, Stringout, StringoutRecord);
+into_any!(AnyWriteRecord, Mbbo, MbboRecord);
+into_any!(AnyWriteRecord, MbboDirect, MbboDirectRecord);
+into_any!(AnyWriteRecord, Aao, AaoRecord);
+into_any!(AnyWriteRecord, Lso, LsoRecord);
+into_any!(AnyWriteRecord, Int64out, Int64outRecord);
+into_any!(AnyWriteRecord, Calcout, CalcoutRecord);
 
 try_from_any!(AnyWriteRecord, Ao, AoRecord);
 try_from_any!(AnyWriteRecord, Bo, BoRecord);
 try_from_any!(AnyWriteRecord, Longout, LongoutRecord);
 try_from_any!(AnyWriteRecord, Stringout, StringoutRecord);
+try_from_any!(AnyWriteRecord, Mbbo, MbboRecord);
+try_from_any!(AnyWriteRecord, MbboDirect, MbboDirectRecord);
+try_from_any!(AnyWriteRecord, Aao, AaoRecord);
+try_from_any!(AnyWriteRecord, Lso, LsoRecord);
+try_from_any!(AnyWriteRecord, Int64out, Int64outRecord);
+try_from_any!(AnyWriteRecord, Calcout, CalcoutRecord);
 
 
 /// Any boxed handler wrapper
@@ -276,6 +602,67 @@ pub enum AnyHandlerBox {
     Longout(Box<dyn LongoutHandler + Send>),
     Stringin(Box<dyn StringinHandler + Send>),
     Stringout(Box<dyn StringoutHandler + Send>),
+    Mbbi(Box<dyn MbbiHandler + Send>),
+    Mbbo(Box<dyn MbboHandler + Send>),
+    MbbiDirect(Box<dyn MbbiDirectHandler + Send>),
+    MbboDirect(Box<dyn MbboDirectHandler + Send>),
+    Waveform(Box<dyn WaveformHandler + Send>),
+    Aai(Box<dyn AaiHandler + Send>),
+    Aao(Box<dyn AaoHandler + Send>),
+    Lsi(Box<dyn LsiHandler + Send>),
+    Lso(Box<dyn LsoHandler + Send>),
+    Int64in(Box<dyn Int64inHandler + Send>),
+    Int64out(Box<dyn Int64outHandler + Send>),
+    Event(Box<dyn EventHandler + Send>),
+    Calcout(Box<dyn CalcoutHandler + Send>),
+    Histogram(Box<dyn HistogramHandler + Send>),
+    SubArray(Box<dyn SubArrayHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncAi(Box<dyn AsyncAiHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncAo(Box<dyn AsyncAoHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncBi(Box<dyn AsyncBiHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncBo(Box<dyn AsyncBoHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncLongin(Box<dyn AsyncLonginHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncLongout(Box<dyn AsyncLongoutHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncStringin(Box<dyn AsyncStringinHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncStringout(Box<dyn AsyncStringoutHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncMbbi(Box<dyn AsyncMbbiHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncMbbo(Box<dyn AsyncMbboHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncMbbiDirect(Box<dyn AsyncMbbiDirectHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncMbboDirect(Box<dyn AsyncMbboDirectHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncWaveform(Box<dyn AsyncWaveformHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncAai(Box<dyn AsyncAaiHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncAao(Box<dyn AsyncAaoHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncLsi(Box<dyn AsyncLsiHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncLso(Box<dyn AsyncLsoHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncInt64in(Box<dyn AsyncInt64inHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncInt64out(Box<dyn AsyncInt64outHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncEvent(Box<dyn AsyncEventHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncCalcout(Box<dyn AsyncCalcoutHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncHistogram(Box<dyn AsyncHistogramHandler + Send>),
+    #[cfg(feature = "async")]
+    AsyncSubArray(Box<dyn AsyncSubArrayHandler + Send>),
 }
 impl AnyHandlerBox {
     pub fn rtype(&self) -> RecordType {
@@ -288,6 +675,67 @@ impl AnyHandlerBox {
             AnyHandlerBox::Longout(_) => RecordType::Longout,
             AnyHandlerBox::Stringin(_) => RecordType::Stringin,
             AnyHandlerBox::Stringout(_) => RecordType::Stringout,
+            AnyHandlerBox::Mbbi(_) => RecordType::Mbbi,
+            AnyHandlerBox::Mbbo(_) => RecordType::Mbbo,
+            AnyHandlerBox::MbbiDirect(_) => RecordType::MbbiDirect,
+            AnyHandlerBox::MbboDirect(_) => RecordType::MbboDirect,
+            AnyHandlerBox::Waveform(_) => RecordType::Waveform,
+            AnyHandlerBox::Aai(_) => RecordType::Aai,
+            AnyHandlerBox::Aao(_) => RecordType::Aao,
+            AnyHandlerBox::Lsi(_) => RecordType::Lsi,
+            AnyHandlerBox::Lso(_) => RecordType::Lso,
+            AnyHandlerBox::Int64in(_) => RecordType::Int64in,
+            AnyHandlerBox::Int64out(_) => RecordType::Int64out,
+            AnyHandlerBox::Event(_) => RecordType::Event,
+            AnyHandlerBox::Calcout(_) => RecordType::Calcout,
+            AnyHandlerBox::Histogram(_) => RecordType::Histogram,
+            AnyHandlerBox::SubArray(_) => RecordType::SubArray,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncAi(_) => RecordType::Ai,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncAo(_) => RecordType::Ao,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncBi(_) => RecordType::Bi,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncBo(_) => RecordType::Bo,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncLongin(_) => RecordType::Longin,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncLongout(_) => RecordType::Longout,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncStringin(_) => RecordType::Stringin,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncStringout(_) => RecordType::Stringout,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncMbbi(_) => RecordType::Mbbi,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncMbbo(_) => RecordType::Mbbo,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncMbbiDirect(_) => RecordType::MbbiDirect,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncMbboDirect(_) => RecordType::MbboDirect,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncWaveform(_) => RecordType::Waveform,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncAai(_) => RecordType::Aai,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncAao(_) => RecordType::Aao,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncLsi(_) => RecordType::Lsi,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncLso(_) => RecordType::Lso,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncInt64in(_) => RecordType::Int64in,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncInt64out(_) => RecordType::Int64out,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncEvent(_) => RecordType::Event,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncCalcout(_) => RecordType::Calcout,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncHistogram(_) => RecordType::Histogram,
+            #[cfg(feature = "async")]
+            AnyHandlerBox::AsyncSubArray(_) => RecordType::SubArray,
         }
     }
 }
@@ -300,6 +748,21 @@ into_any!(AnyHandlerBox, Longin, Box<dyn LonginHandler + Send>);
 into_any!(AnyHandlerBox, Longout, Box<dyn LongoutHandler + Send>);
 into_any!(AnyHandlerBox, Stringin, Box<dyn StringinHandler + Send>);
 into_any!(AnyHandlerBox, Stringout, Box<dyn StringoutHandler + Send>);
+into_any!(AnyHandlerBox, Mbbi, Box<dyn MbbiHandler + Send>);
+into_any!(AnyHandlerBox, Mbbo, Box<dyn MbboHandler + Send>);
+into_any!(AnyHandlerBox, MbbiDirect, Box<dyn MbbiDirectHandler + Send>);
+into_any!(AnyHandlerBox, MbboDirect, Box<dyn MbboDirectHandler + Send>);
+into_any!(AnyHandlerBox, Waveform, Box<dyn WaveformHandler + Send>);
+into_any!(AnyHandlerBox, Aai, Box<dyn AaiHandler + Send>);
+into_any!(AnyHandlerBox, Aao, Box<dyn AaoHandler + Send>);
+into_any!(AnyHandlerBox, Lsi, Box<dyn LsiHandler + Send>);
+into_any!(AnyHandlerBox, Lso, Box<dyn LsoHandler + Send>);
+into_any!(AnyHandlerBox, Int64in, Box<dyn Int64inHandler + Send>);
+into_any!(AnyHandlerBox, Int64out, Box<dyn Int64outHandler + Send>);
+into_any!(AnyHandlerBox, Event, Box<dyn EventHandler + Send>);
+into_any!(AnyHandlerBox, Calcout, Box<dyn CalcoutHandler + Send>);
+into_any!(AnyHandlerBox, Histogram, Box<dyn HistogramHandler + Send>);
+into_any!(AnyHandlerBox, SubArray, Box<dyn SubArrayHandler + Send>);
 
 try_from_any!(AnyHandlerBox, Ai, Box<dyn AiHandler + Send>);
 try_from_any!(AnyHandlerBox, Ao, Box<dyn AoHandler + Send>);
@@ -309,3 +772,112 @@ try_from_any!(AnyHandlerBox, Longin, Box<dyn LonginHandler + Send>);
 try_from_any!(AnyHandlerBox, Longout, Box<dyn LongoutHandler + Send>);
 try_from_any!(AnyHandlerBox, Stringin, Box<dyn StringinHandler + Send>);
 try_from_any!(AnyHandlerBox, Stringout, Box<dyn StringoutHandler + Send>);
+try_from_any!(AnyHandlerBox, Mbbi, Box<dyn MbbiHandler + Send>);
+try_from_any!(AnyHandlerBox, Mbbo, Box<dyn MbboHandler + Send>);
+try_from_any!(AnyHandlerBox, MbbiDirect, Box<dyn MbbiDirectHandler + Send>);
+try_from_any!(AnyHandlerBox, MbboDirect, Box<dyn MbboDirectHandler + Send>);
+try_from_any!(AnyHandlerBox, Waveform, Box<dyn WaveformHandler + Send>);
+try_from_any!(AnyHandlerBox, Aai, Box<dyn AaiHandler + Send>);
+try_from_any!(AnyHandlerBox, Aao, Box<dyn AaoHandler + Send>);
+try_from_any!(AnyHandlerBox, Lsi, Box<dyn LsiHandler + Send>);
+try_from_any!(AnyHandlerBox, Lso, Box<dyn LsoHandler + Send>);
+try_from_any!(AnyHandlerBox, Int64in, Box<dyn Int64inHandler + Send>);
+try_from_any!(AnyHandlerBox, Int64out, Box<dyn Int64outHandler + Send>);
+try_from_any!(AnyHandlerBox, Event, Box<dyn EventHandler + Send>);
+try_from_any!(AnyHandlerBox, Calcout, Box<dyn CalcoutHandler + Send>);
+try_from_any!(AnyHandlerBox, Histogram, Box<dyn HistogramHandler + Send>);
+try_from_any!(AnyHandlerBox, SubArray, Box<dyn SubArrayHandler + Send>);
+
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncAi, Box<dyn AsyncAiHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncAo, Box<dyn AsyncAoHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncBi, Box<dyn AsyncBiHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncBo, Box<dyn AsyncBoHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncLongin, Box<dyn AsyncLonginHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncLongout, Box<dyn AsyncLongoutHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncStringin, Box<dyn AsyncStringinHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncStringout, Box<dyn AsyncStringoutHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncMbbi, Box<dyn AsyncMbbiHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncMbbo, Box<dyn AsyncMbboHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncMbbiDirect, Box<dyn AsyncMbbiDirectHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncMbboDirect, Box<dyn AsyncMbboDirectHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncWaveform, Box<dyn AsyncWaveformHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncAai, Box<dyn AsyncAaiHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncAao, Box<dyn AsyncAaoHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncLsi, Box<dyn AsyncLsiHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncLso, Box<dyn AsyncLsoHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncInt64in, Box<dyn AsyncInt64inHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncInt64out, Box<dyn AsyncInt64outHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncEvent, Box<dyn AsyncEventHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncCalcout, Box<dyn AsyncCalcoutHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncHistogram, Box<dyn AsyncHistogramHandler + Send>);
+#[cfg(feature = "async")]
+into_any!(AnyHandlerBox, AsyncSubArray, Box<dyn AsyncSubArrayHandler + Send>);
+
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncAi, Box<dyn AsyncAiHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncAo, Box<dyn AsyncAoHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncBi, Box<dyn AsyncBiHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncBo, Box<dyn AsyncBoHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncLongin, Box<dyn AsyncLonginHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncLongout, Box<dyn AsyncLongoutHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncStringin, Box<dyn AsyncStringinHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncStringout, Box<dyn AsyncStringoutHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncMbbi, Box<dyn AsyncMbbiHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncMbbo, Box<dyn AsyncMbboHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncMbbiDirect, Box<dyn AsyncMbbiDirectHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncMbboDirect, Box<dyn AsyncMbboDirectHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncWaveform, Box<dyn AsyncWaveformHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncAai, Box<dyn AsyncAaiHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncAao, Box<dyn AsyncAaoHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncLsi, Box<dyn AsyncLsiHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncLso, Box<dyn AsyncLsoHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncInt64in, Box<dyn AsyncInt64inHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncInt64out, Box<dyn AsyncInt64outHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncEvent, Box<dyn AsyncEventHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncCalcout, Box<dyn AsyncCalcoutHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncHistogram, Box<dyn AsyncHistogramHandler + Send>);
+#[cfg(feature = "async")]
+try_from_any!(AnyHandlerBox, AsyncSubArray, Box<dyn AsyncSubArrayHandler + Send>);
@@ -1,9 +1,11 @@
 use std::ops::DerefMut;
+use std::time::{SystemTime, Duration};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use libc::{c_int, c_void};
 
 use epics_sys::{
-    dbCommon,
+    dbCommon, epicsTimeStamp,
     CALLBACK, callbackSetProcess,
 };
 
@@ -11,9 +13,14 @@ use crate::{
     record::{
         Scan, Callback,
         RecordType,
+        AlarmStatus, AlarmSeverity,
     },
     util::{cstr_array_read},
+    time,
+    async_proc::{self, QueueHandle, CancelToken},
 };
+#[cfg(feature = "async")]
+use crate::record::HandlerFuture;
 
 
 /// Private data in record
@@ -24,6 +31,48 @@ pub struct CommonPrivate {
     rtype: RecordType,
     callback: Callback,
     scan: Option<Scan>,
+    scan_attached: bool,
+    convert: bool,
+    queue: Option<QueueHandle>,
+    timeout: Option<Duration>,
+    token: CancelToken,
+    busy: AtomicBool,
+    failure: Option<String>,
+}
+impl CommonPrivate {
+    /// Request the record to convert raw value on return from handler
+    pub(crate) fn request_convert(&mut self) {
+        self.convert = true;
+    }
+    pub(crate) fn take_convert(&mut self) -> bool {
+        let convert = self.convert;
+        self.convert = false;
+        convert
+    }
+    pub(crate) fn queue(&self) -> Option<QueueHandle> {
+        self.queue.clone()
+    }
+    pub(crate) fn set_cancel_token(&mut self, token: CancelToken) {
+        self.token = token;
+    }
+    /// Mark handler as checked out by asynchronous worker
+    pub(crate) fn set_busy(&self, busy: bool) {
+        self.busy.store(busy, Ordering::SeqCst);
+    }
+    /// Handler is checked out by asynchronous worker,
+    /// e.g. its request has timed out but it has not returned yet
+    pub(crate) fn is_busy(&self) -> bool {
+        self.busy.load(Ordering::SeqCst)
+    }
+    pub(crate) fn callback_ptr(&mut self) -> *mut Callback {
+        &mut self.callback as *mut _
+    }
+    pub(crate) fn set_failure(&mut self, msg: String) {
+        self.failure = Some(msg);
+    }
+    pub(crate) fn clear_failure(&mut self) -> Option<String> {
+        self.failure.take()
+    }
 }
 
 /// Record that could be emerged from raw pointer
@@ -47,6 +96,24 @@ pub(crate) unsafe fn raw_private_create(raw: &mut dbCommon, rtype: RecordType) -
         rtype,
         callback: Callback::new(cb),
         scan: None,
+        scan_attached: false,
+        convert: false,
+        queue: None,
+        timeout: None,
+        token: CancelToken::default(),
+        busy: AtomicBool::new(false),
+        failure: None,
+    }
+}
+
+/// Raise alarm on raw record, see `Record::set_alarm`
+pub(crate) fn raw_set_alarm(raw: &mut dbCommon, status: AlarmStatus, severity: AlarmSeverity) -> bool {
+    if severity.as_raw() > raw.nsev as u16 {
+        raw.nsta = status.as_raw() as _;
+        raw.nsev = severity.as_raw() as _;
+        true
+    } else {
+        false
     }
 }
 
@@ -66,6 +133,12 @@ pub trait Record: Linked {
     unsafe fn as_raw_mut(&mut self) -> &mut dbCommon;
 
     unsafe fn init(&mut self);
+    unsafe fn handler_shutdown(&mut self) -> Option<()>;
+    /// Create another wrapper of the same record.
+    ///
+    /// Caller should ensure that the record is not accessed concurrently,
+    /// e.g. while it is owned by asynchronous processing with PACT set.
+    unsafe fn duplicate(&self) -> Self where Self: Sized;
 
     fn rtype(&self) -> RecordType {
         unsafe { self.private() }.rtype
@@ -82,9 +155,110 @@ pub trait Record: Linked {
         self.as_raw_mut().pact = if pact { 1 } else { 0 };
     }
 
+    /// Raise alarm with `recGblSetSevr` semantics:
+    /// status and severity become pending only if the severity
+    /// is greater than the pending one.
+    ///
+    /// Returns `true` if the alarm was accepted.
+    fn set_alarm(&mut self, status: AlarmStatus, severity: AlarmSeverity) -> bool {
+        raw_set_alarm(unsafe { self.as_raw_mut() }, status, severity)
+    }
+    /// Pending alarm status (NSTA)
+    fn nsta(&self) -> Option<AlarmStatus> {
+        AlarmStatus::from_raw(unsafe { self.as_raw() }.nsta as u16)
+    }
+    /// Pending alarm severity (NSEV)
+    fn nsev(&self) -> Option<AlarmSeverity> {
+        AlarmSeverity::from_raw(unsafe { self.as_raw() }.nsev as u16)
+    }
+    /// Current alarm status (STAT)
+    fn stat(&self) -> Option<AlarmStatus> {
+        AlarmStatus::from_raw(unsafe { self.as_raw() }.stat as u16)
+    }
+    /// Current alarm severity (SEVR)
+    fn sevr(&self) -> Option<AlarmSeverity> {
+        AlarmSeverity::from_raw(unsafe { self.as_raw() }.sevr as u16)
+    }
+
+    /// Time stamp event (TSE).
+    ///
+    /// Time stamp set by handler is kept by the record only if TSE is -2,
+    /// otherwise it is overwritten after read or write.
+    fn tse(&self) -> i16 {
+        unsafe { self.as_raw() }.tse as i16
+    }
+    /// Record time stamp (TIME)
+    fn time(&self) -> SystemTime {
+        time::from_epics(&unsafe { self.as_raw() }.time)
+    }
+    /// Set record time stamp (TIME)
+    ///
+    /// Fails if time cannot be represented by EPICS time stamp.
+    fn set_time(&mut self, time: SystemTime) -> crate::Result<()> {
+        let ts = time::to_epics(time).ok_or_else(|| crate::Error::Other(format!(
+            "record({}): time {:?} is out of EPICS time stamp range",
+            self.name(), time,
+        )))?;
+        self.set_time_raw(ts);
+        Ok(())
+    }
+    /// Record time stamp (TIME) as is
+    fn time_raw(&self) -> epicsTimeStamp {
+        unsafe { self.as_raw() }.time
+    }
+    fn set_time_raw(&mut self, ts: epicsTimeStamp) {
+        unsafe { self.as_raw_mut() }.time = ts;
+    }
+
     unsafe fn private(&self) -> &Private;
     unsafe fn private_mut(&mut self) -> &mut Private;
 
+    /// Perform asynchronous requests of the record in named work queue
+    /// created by `Context::create_queue` instead of the common pool.
+    ///
+    /// Should be called from `InitHandler::init`.
+    fn bind_queue(&mut self, name: &str) -> crate::Result<()> {
+        let queue = async_proc::find_queue(name)?;
+        unsafe { self.private_mut() }.queue = Some(queue);
+        Ok(())
+    }
+    /// Name of work queue the record is bound to
+    fn queue_name(&self) -> Option<String> {
+        unsafe { self.private() }.queue.as_ref().map(|q| q.name().to_string())
+    }
+
+    /// Set timeout of asynchronous requests, `None` means no timeout.
+    ///
+    /// If `read_async` or `write_async` does not return in time,
+    /// the record is completed with TIMEOUT/INVALID alarm
+    /// and the cancel token of the request is cancelled.
+    /// Until the handler returns, the record is completed
+    /// with the same alarm without calling the handler.
+    ///
+    /// Timed out record is processed by EPICS while the handler may still run,
+    /// so the handler *must not touch the record* once the cancel token
+    /// is cancelled, e.g. it should read hardware into local variables
+    /// and check the token before storing them into the record.
+    fn set_async_timeout(&mut self, timeout: Option<Duration>) {
+        unsafe { self.private_mut() }.timeout = timeout;
+    }
+    fn async_timeout(&self) -> Option<Duration> {
+        unsafe { self.private() }.timeout
+    }
+    /// Cancel token of current asynchronous request
+    fn cancel_token(&self) -> CancelToken {
+        unsafe { self.private() }.token.clone()
+    }
+
+    /// Reason why the record is disabled, if it is.
+    ///
+    /// Record is disabled when its initialization fails or its handler panics.
+    /// Disabled record is left active (PACT is set), so it is not processed
+    /// until it is restarted, and has DISABLE/INVALID alarm.
+    fn failure(&self) -> Option<&str> {
+        unsafe { self.private() }.failure.as_ref().map(|s| s.as_str())
+    }
+
     unsafe fn process(&mut self) -> Result<(),()> {
         let pvt = self.private_mut();
         pvt.callback.request()
@@ -96,22 +270,62 @@ pub trait ScanRecord: Record {
     unsafe fn create_scan(&self) -> Scan {
         Scan::new()
     }
-    unsafe fn set_scan(&mut self, scan: Scan) {
-        assert!(self.private_mut().scan.replace(scan).is_none());
+    /// Mark record as attached to `I/O Intr` scan list and return its scan handle.
+    ///
+    /// The handle is created on the first call and reused on later ones.
+    unsafe fn attach_scan(&mut self) -> Scan {
+        let scan = match self.private().scan.clone() {
+            Some(scan) => scan,
+            None => {
+                let scan = self.create_scan();
+                self.private_mut().scan = Some(scan.clone());
+                scan
+            },
+        };
+        self.private_mut().scan_attached = true;
+        scan
+    }
+    /// Store scan handle, returns previous one if any
+    unsafe fn set_scan(&mut self, scan: Scan) -> Option<Scan> {
+        let pvt = self.private_mut();
+        pvt.scan_attached = true;
+        pvt.scan.replace(scan)
+    }
+    /// Mark record as detached from scan list.
+    ///
+    /// Returns scan handle if the record was attached,
+    /// the handle is kept for the next attachment.
+    unsafe fn unset_scan(&mut self) -> Option<Scan> {
+        let pvt = self.private_mut();
+        if !pvt.scan_attached {
+            return None;
+        }
+        pvt.scan_attached = false;
+        pvt.scan.clone()
     }
+    /// Scan handle if the record is attached to scan list
     unsafe fn get_scan(&self) -> Option<Scan> {
-        self.private().scan.clone()
+        let pvt = self.private();
+        if pvt.scan_attached { pvt.scan.clone() } else { None }
     }
     unsafe fn handler_set_scan(&mut self, scan: Scan) -> Option<crate::Result<()>>;
+    unsafe fn handler_unset_scan(&mut self) -> Option<crate::Result<()>>;
+}
+
+/// Record with linear conversion of raw value
+pub trait LinconvRecord: Record {
+    unsafe fn handler_linconv(&mut self, after: bool) -> Option<crate::Result<()>>;
 }
 
 /// Readable record behavior
 pub trait ReadRecord: Record {
-    /// Return text field of INP link if its type is CONSTANT
+    /// Return text field of INP link (SVL for histogram) if its type is INST_IO
     fn inp(&self) -> &str;
 
     unsafe fn handler_read(&mut self) -> Option<crate::Result<bool>>;
     unsafe fn handler_read_async(&mut self) -> Option<crate::Result<()>>;
+    #[cfg(feature = "async")]
+    unsafe fn handler_read_future(&mut self) -> Option<crate::Result<HandlerFuture>>;
 }
 
 /// Writable record behavior
@@ -121,4 +335,6 @@ pub trait WriteRecord: Record {
 
     unsafe fn handler_write(&mut self) -> Option<crate::Result<bool>>;
     unsafe fn handler_write_async(&mut self) -> Option<crate::Result<()>>;
+    #[cfg(feature = "async")]
+    unsafe fn handler_write_future(&mut self) -> Option<crate::Result<HandlerFuture>>;
 }
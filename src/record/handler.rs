@@ -1,10 +1,19 @@
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
 use crate::record::{
     Scan, Record,
     ScanRecord, ReadRecord, WriteRecord,
 };
 
 /// Base of all handlers
-pub trait Handler<R: Record> {}
+pub trait Handler<R: Record> {
+    /// Called once on IOC exit after asynchronous processing is stopped.
+    ///
+    /// Handler should release its resources here, e.g. close connections.
+    /// Does nothing by default.
+    fn shutdown(&mut self, _rec: &mut R) {}
+}
 
 /// Handler that able to be initialized
 pub trait InitHandler<R: Record>: Handler<R> {
@@ -19,6 +28,14 @@ pub trait InitHandler<R: Record>: Handler<R> {
 pub trait ScanHandler<R: ScanRecord>: Handler<R> {
     /// Set scan handle for `I/O Intr` records.
     fn set_scan(&mut self, rec: &mut R, scan: Scan) -> crate::Result<()>;
+    /// Called when record is removed from `I/O Intr` scan list,
+    /// e.g. when its SCAN field is changed at runtime.
+    ///
+    /// Scan handle passed to `set_scan` should not be used after that.
+    /// Does nothing by default.
+    fn unset_scan(&mut self, _rec: &mut R) -> crate::Result<()> {
+        Ok(())
+    }
 }
 
 /// Handler for records that could be read
@@ -33,6 +50,8 @@ pub trait ReadHandler<R: ReadRecord>: Handler<R> {
     ///
     /// This operation is performed in separate thread
     /// from thread pool and then notifies the EPICS.
+    /// The record must not be accessed after its cancel token
+    /// is cancelled, see `Record::set_async_timeout`.
     fn read_async(&mut self, rec: &mut R) -> crate::Result<()>;
 }
 
@@ -48,5 +67,40 @@ pub trait WriteHandler<R: WriteRecord>: Handler<R> {
     ///
     /// This operation is performed in separate thread
     /// from thread pool and then notifies the EPICS.
+    /// The record must not be accessed after its cancel token
+    /// is cancelled, see `Record::set_async_timeout`.
     fn write_async(&mut self, rec: &mut R) -> crate::Result<()>;
 }
+
+/// Future of asynchronous request returned by handler
+#[cfg(feature = "async")]
+pub type HandlerFuture = Pin<Box<dyn Future<Output=crate::Result<()>> + Send + 'static>>;
+
+/// Handler for records that could be read by future.
+///
+/// It is used instead of `ReadHandler` by record-specific async handler
+/// traits, e.g. `AsyncAiHandler`.
+#[cfg(feature = "async")]
+pub trait AsyncReadHandler<R: ReadRecord>: Handler<R> {
+    /// Start asynchronous read request. *Should not block.*
+    ///
+    /// Returned future owns the record until it is completed,
+    /// then the record is processed.
+    /// The future is run in the work queue the record is bound to
+    /// or by the runtime of device support otherwise.
+    /// It is subject to `Record::set_async_timeout`,
+    /// pending futures are cancelled on IOC exit.
+    fn read_future(&mut self, rec: R) -> crate::Result<HandlerFuture>;
+}
+
+/// Handler for records that could be written by future.
+///
+/// It is used instead of `WriteHandler` by record-specific async handler
+/// traits, e.g. `AsyncAoHandler`.
+#[cfg(feature = "async")]
+pub trait AsyncWriteHandler<R: WriteRecord>: Handler<R> {
+    /// Start asynchronous write request. *Should not block.*
+    ///
+    /// See `AsyncReadHandler::read_future`.
+    fn write_future(&mut self, rec: R) -> crate::Result<HandlerFuture>;
+}
@@ -3,24 +3,106 @@ use epics_sys::{aiRecord, aoRecord};
 use crate::record::{
     Scan, RecordType, FromRaw, Private, CommonPrivate,
     ScanHandler, ReadHandler, WriteHandler,
+    LinconvRecord,
 };
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
+
+
+macro_rules! impl_linconv_fields {
+    ($Record:ident) => {
+        impl $Record {
+            /// Raw value
+            pub fn rval(&self) -> i32 {
+                self.raw.rval
+            }
+            /// Raw offset
+            pub fn roff(&self) -> u32 {
+                self.raw.roff as u32
+            }
+            /// Adjustment slope
+            pub fn aslo(&self) -> f64 {
+                self.raw.aslo
+            }
+            /// Adjustment offset
+            pub fn aoff(&self) -> f64 {
+                self.raw.aoff
+            }
+            /// Engineering units slope
+            pub fn eslo(&self) -> f64 {
+                self.raw.eslo
+            }
+            pub fn set_eslo(&mut self, eslo: f64) {
+                self.raw.eslo = eslo;
+            }
+            /// Engineering units offset
+            pub fn eoff(&self) -> f64 {
+                self.raw.eoff
+            }
+            pub fn set_eoff(&mut self, eoff: f64) {
+                self.raw.eoff = eoff;
+            }
+            /// Engineering units full scale
+            pub fn eguf(&self) -> f64 {
+                self.raw.eguf
+            }
+            /// Engineering units low scale
+            pub fn egul(&self) -> f64 {
+                self.raw.egul
+            }
+            /// Linearization type, raw value of `menuConvert`
+            pub fn linr(&self) -> u16 {
+                self.raw.linr as u16
+            }
+        }
+        impl LinconvRecord for $Record {
+            unsafe fn handler_linconv(&mut self, after: bool) -> Option<crate::Result<()>> {
+                with_any_handler!(self, |h, r| h.linconv(r, after))
+            }
+        }
+    };
+}
 
 
 // Analog input
 
 /// Handler trait for analog input
 pub trait AiHandler: ScanHandler<AiRecord> + ReadHandler<AiRecord> {
+    /// Called when linear conversion parameters are going to change
+    /// (`after == false`) and when they have been changed (`after == true`).
+    ///
+    /// Handler may update ESLO and EOFF here. Does nothing by default.
+    fn linconv(&mut self, _rec: &mut AiRecord, _after: bool) -> crate::Result<()> {
+        Ok(())
+    }
     impl_into_boxed_handler!(AiHandler);
 }
 
+/// Handler trait for analog input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncAiHandler: ScanHandler<AiRecord> + AsyncReadHandler<AiRecord> {
+    /// See `AiHandler::linconv`
+    fn linconv(&mut self, _rec: &mut AiRecord, _after: bool) -> crate::Result<()> {
+        Ok(())
+    }
+    impl_into_boxed_handler!(AsyncAiHandler);
+}
+
 /// Analog input private data
 pub struct AiPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn AiHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncAiHandler + Send>>,
 }
 impl AiPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(AiPrivate, CommonPrivate, base);
@@ -37,9 +119,17 @@ impl AiRecord {
     pub fn set_val(&mut self, val: f64) {
         self.raw.val = val;
     }
+    /// Set raw value.
+    ///
+    /// The record will convert it to VAL when the read is complete.
+    pub fn set_rval(&mut self, rval: i32) {
+        self.raw.rval = rval;
+        unsafe { self.private_mut() }.request_convert();
+    }
 }
+impl_linconv_fields!(AiRecord);
 impl_record_private!(AiRecord, AiPrivate);
-impl_record_handler!(AiRecord, AiHandler);
+impl_record_handler!(AiRecord, AiHandler, AsyncAiHandler);
 
 impl FromRaw for AiRecord {
     type Raw = *mut aiRecord;
@@ -59,23 +149,49 @@ unsafe impl Send for AiRecord {}
 
 /// Handler trait for analog output
 pub trait AoHandler: ScanHandler<AoRecord> + WriteHandler<AoRecord> {
+    /// Called when linear conversion parameters are going to change
+    /// (`after == false`) and when they have been changed (`after == true`).
+    ///
+    /// Handler may update ESLO and EOFF here. Does nothing by default.
+    fn linconv(&mut self, _rec: &mut AoRecord, _after: bool) -> crate::Result<()> {
+        Ok(())
+    }
     impl_into_boxed_handler!(AoHandler);
 }
 
+/// Handler trait for analog output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncAoHandler: ScanHandler<AoRecord> + AsyncWriteHandler<AoRecord> {
+    /// See `AoHandler::linconv`
+    fn linconv(&mut self, _rec: &mut AoRecord, _after: bool) -> crate::Result<()> {
+        Ok(())
+    }
+    impl_into_boxed_handler!(AsyncAoHandler);
+}
+
 /// Analog output private data
 pub struct AoPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn AoHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncAoHandler + Send>>,
 }
 impl AoPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(AoPrivate, CommonPrivate, base);
 impl Private for AoPrivate {}
 
 /// Analog output record
+///
+/// RVAL is computed by the record from VAL before write.
 pub struct AoRecord {
     raw: &'static mut aoRecord,
 }
@@ -86,9 +202,18 @@ impl AoRecord {
     pub fn set_val(&mut self, val: f64) {
         self.raw.val = val;
     }
+    /// Set raw value on record initialization.
+    ///
+    /// The record will convert it to VAL when the initialization is complete.
+    /// Outside of initialization only RVAL is set.
+    pub fn set_rval(&mut self, rval: i32) {
+        self.raw.rval = rval;
+        unsafe { self.private_mut() }.request_convert();
+    }
 }
+impl_linconv_fields!(AoRecord);
 impl_record_private!(AoRecord, AoPrivate);
-impl_record_handler!(AoRecord, AoHandler);
+impl_record_handler!(AoRecord, AoHandler, AsyncAoHandler);
 
 impl FromRaw for AoRecord {
     type Raw = *mut aoRecord;
@@ -0,0 +1,122 @@
+use epics_sys::{aaiRecord, aaoRecord};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler, WriteHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
+
+
+// Array analog input
+
+/// Handler trait for array analog input
+pub trait AaiHandler: ScanHandler<AaiRecord> + ReadHandler<AaiRecord> {
+    impl_into_boxed_handler!(AaiHandler);
+}
+
+/// Handler trait for array analog input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncAaiHandler: ScanHandler<AaiRecord> + AsyncReadHandler<AaiRecord> {
+    impl_into_boxed_handler!(AsyncAaiHandler);
+}
+
+/// Array analog input private data
+pub struct AaiPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn AaiHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncAaiHandler + Send>>,
+}
+impl AaiPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(AaiPrivate, CommonPrivate, base);
+impl Private for AaiPrivate {}
+
+/// Array analog input record
+///
+/// Handler should fill the buffer and set NORD to the number of elements read.
+pub struct AaiRecord {
+    raw: &'static mut aaiRecord,
+}
+impl_array_fields!(AaiRecord);
+impl_record_private!(AaiRecord, AaiPrivate);
+impl_record_handler!(AaiRecord, AaiHandler, AsyncAaiHandler);
+
+impl FromRaw for AaiRecord {
+    type Raw = *mut aaiRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(AaiRecord, Aai);
+derive_record!(AaiRecord, AaiPrivate);
+derive_scan_record!(AaiRecord);
+derive_read_record!(AaiRecord);
+derive_deref_record!(AaiRecord);
+unsafe impl Send for AaiRecord {}
+
+
+// Array analog output
+
+/// Handler trait for array analog output
+pub trait AaoHandler: ScanHandler<AaoRecord> + WriteHandler<AaoRecord> {
+    impl_into_boxed_handler!(AaoHandler);
+}
+
+/// Handler trait for array analog output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncAaoHandler: ScanHandler<AaoRecord> + AsyncWriteHandler<AaoRecord> {
+    impl_into_boxed_handler!(AsyncAaoHandler);
+}
+
+/// Array analog output private data
+pub struct AaoPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn AaoHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncAaoHandler + Send>>,
+}
+impl AaoPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(AaoPrivate, CommonPrivate, base);
+impl Private for AaoPrivate {}
+
+/// Array analog output record
+///
+/// Handler should write NORD elements of the buffer.
+pub struct AaoRecord {
+    raw: &'static mut aaoRecord,
+}
+impl_array_fields!(AaoRecord);
+impl_record_private!(AaoRecord, AaoPrivate);
+impl_record_handler!(AaoRecord, AaoHandler, AsyncAaoHandler);
+
+impl FromRaw for AaoRecord {
+    type Raw = *mut aaoRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(AaoRecord, Aao);
+derive_record!(AaoRecord, AaoPrivate);
+derive_scan_record!(AaoRecord);
+derive_write_record!(AaoRecord);
+derive_deref_record!(AaoRecord);
+unsafe impl Send for AaoRecord {}
@@ -0,0 +1,200 @@
+use std::slice;
+
+use libc::{c_char, c_void};
+use epics_sys::{
+    menuFtype_menuFtypeSTRING,
+    menuFtype_menuFtypeCHAR, menuFtype_menuFtypeUCHAR,
+    menuFtype_menuFtypeSHORT, menuFtype_menuFtypeUSHORT,
+    menuFtype_menuFtypeLONG, menuFtype_menuFtypeULONG,
+    menuFtype_menuFtypeFLOAT, menuFtype_menuFtypeDOUBLE,
+};
+
+
+/// Size of string element of array including trailing zero
+pub const ARRAY_STRING_SIZE: usize = 40;
+
+/// String element of array
+pub type ArrayString = [c_char; ARRAY_STRING_SIZE];
+
+/// Type of array elements (FTVL field)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrayType {
+    String,
+    Char,
+    Uchar,
+    Short,
+    Ushort,
+    Long,
+    Ulong,
+    Float,
+    Double,
+}
+impl ArrayType {
+    pub(crate) fn from_raw(ftvl: u32) -> Option<Self> {
+        [
+            (menuFtype_menuFtypeSTRING as u32, ArrayType::String),
+            (menuFtype_menuFtypeCHAR as u32, ArrayType::Char),
+            (menuFtype_menuFtypeUCHAR as u32, ArrayType::Uchar),
+            (menuFtype_menuFtypeSHORT as u32, ArrayType::Short),
+            (menuFtype_menuFtypeUSHORT as u32, ArrayType::Ushort),
+            (menuFtype_menuFtypeLONG as u32, ArrayType::Long),
+            (menuFtype_menuFtypeULONG as u32, ArrayType::Ulong),
+            (menuFtype_menuFtypeFLOAT as u32, ArrayType::Float),
+            (menuFtype_menuFtypeDOUBLE as u32, ArrayType::Double),
+        ].iter().find(|(raw, _)| *raw == ftvl).map(|(_, t)| *t)
+    }
+}
+
+/// Type that could be an element of array
+pub trait ArrayElement: Copy {
+    fn atype() -> ArrayType;
+}
+macro_rules! impl_array_element {
+    ($T:ty, $t:ident) => {
+        impl ArrayElement for $T {
+            fn atype() -> ArrayType {
+                ArrayType::$t
+            }
+        }
+    };
+}
+impl_array_element!(ArrayString, String);
+impl_array_element!(i8, Char);
+impl_array_element!(u8, Uchar);
+impl_array_element!(i16, Short);
+impl_array_element!(u16, Ushort);
+impl_array_element!(i32, Long);
+impl_array_element!(u32, Ulong);
+impl_array_element!(f32, Float);
+impl_array_element!(f64, Double);
+
+/// Typed view of array buffer
+#[derive(Debug)]
+pub enum ArrayRef<'a> {
+    String(&'a [ArrayString]),
+    Char(&'a [i8]),
+    Uchar(&'a [u8]),
+    Short(&'a [i16]),
+    Ushort(&'a [u16]),
+    Long(&'a [i32]),
+    Ulong(&'a [u32]),
+    Float(&'a [f32]),
+    Double(&'a [f64]),
+}
+
+/// Typed mutable view of array buffer
+#[derive(Debug)]
+pub enum ArrayMut<'a> {
+    String(&'a mut [ArrayString]),
+    Char(&'a mut [i8]),
+    Uchar(&'a mut [u8]),
+    Short(&'a mut [i16]),
+    Ushort(&'a mut [u16]),
+    Long(&'a mut [i32]),
+    Ulong(&'a mut [u32]),
+    Float(&'a mut [f32]),
+    Double(&'a mut [f64]),
+}
+
+macro_rules! match_array {
+    ($Array:ident, $a:expr, $x:ident => $e:expr) => {
+        match $a {
+            $Array::String($x) => $e,
+            $Array::Char($x) => $e,
+            $Array::Uchar($x) => $e,
+            $Array::Short($x) => $e,
+            $Array::Ushort($x) => $e,
+            $Array::Long($x) => $e,
+            $Array::Ulong($x) => $e,
+            $Array::Float($x) => $e,
+            $Array::Double($x) => $e,
+        }
+    };
+}
+
+impl<'a> ArrayRef<'a> {
+    pub(crate) unsafe fn from_raw(atype: ArrayType, ptr: *const c_void, len: usize) -> Self {
+        let len = if ptr.is_null() { 0 } else { len };
+        match atype {
+            ArrayType::String => ArrayRef::String(raw_slice(ptr, len)),
+            ArrayType::Char => ArrayRef::Char(raw_slice(ptr, len)),
+            ArrayType::Uchar => ArrayRef::Uchar(raw_slice(ptr, len)),
+            ArrayType::Short => ArrayRef::Short(raw_slice(ptr, len)),
+            ArrayType::Ushort => ArrayRef::Ushort(raw_slice(ptr, len)),
+            ArrayType::Long => ArrayRef::Long(raw_slice(ptr, len)),
+            ArrayType::Ulong => ArrayRef::Ulong(raw_slice(ptr, len)),
+            ArrayType::Float => ArrayRef::Float(raw_slice(ptr, len)),
+            ArrayType::Double => ArrayRef::Double(raw_slice(ptr, len)),
+        }
+    }
+    pub fn atype(&self) -> ArrayType {
+        match self {
+            ArrayRef::String(_) => ArrayType::String,
+            ArrayRef::Char(_) => ArrayType::Char,
+            ArrayRef::Uchar(_) => ArrayType::Uchar,
+            ArrayRef::Short(_) => ArrayType::Short,
+            ArrayRef::Ushort(_) => ArrayType::Ushort,
+            ArrayRef::Long(_) => ArrayType::Long,
+            ArrayRef::Ulong(_) => ArrayType::Ulong,
+            ArrayRef::Float(_) => ArrayType::Float,
+            ArrayRef::Double(_) => ArrayType::Double,
+        }
+    }
+    pub fn len(&self) -> usize {
+        match_array!(ArrayRef, self, a => a.len())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<'a> ArrayMut<'a> {
+    pub(crate) unsafe fn from_raw(atype: ArrayType, ptr: *mut c_void, len: usize) -> Self {
+        let len = if ptr.is_null() { 0 } else { len };
+        match atype {
+            ArrayType::String => ArrayMut::String(raw_slice_mut(ptr, len)),
+            ArrayType::Char => ArrayMut::Char(raw_slice_mut(ptr, len)),
+            ArrayType::Uchar => ArrayMut::Uchar(raw_slice_mut(ptr, len)),
+            ArrayType::Short => ArrayMut::Short(raw_slice_mut(ptr, len)),
+            ArrayType::Ushort => ArrayMut::Ushort(raw_slice_mut(ptr, len)),
+            ArrayType::Long => ArrayMut::Long(raw_slice_mut(ptr, len)),
+            ArrayType::Ulong => ArrayMut::Ulong(raw_slice_mut(ptr, len)),
+            ArrayType::Float => ArrayMut::Float(raw_slice_mut(ptr, len)),
+            ArrayType::Double => ArrayMut::Double(raw_slice_mut(ptr, len)),
+        }
+    }
+    pub fn atype(&self) -> ArrayType {
+        match self {
+            ArrayMut::String(_) => ArrayType::String,
+            ArrayMut::Char(_) => ArrayType::Char,
+            ArrayMut::Uchar(_) => ArrayType::Uchar,
+            ArrayMut::Short(_) => ArrayType::Short,
+            ArrayMut::Ushort(_) => ArrayType::Ushort,
+            ArrayMut::Long(_) => ArrayType::Long,
+            ArrayMut::Ulong(_) => ArrayType::Ulong,
+            ArrayMut::Float(_) => ArrayType::Float,
+            ArrayMut::Double(_) => ArrayType::Double,
+        }
+    }
+    pub fn len(&self) -> usize {
+        match_array!(ArrayMut, self, a => a.len())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub(crate) unsafe fn raw_slice<'a, T>(ptr: *const c_void, len: usize) -> &'a [T] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr as *const T, len)
+    }
+}
+pub(crate) unsafe fn raw_slice_mut<'a, T>(ptr: *mut c_void, len: usize) -> &'a mut [T] {
+    if len == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(ptr as *mut T, len)
+    }
+}
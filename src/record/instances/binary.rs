@@ -5,6 +5,8 @@ use crate::record::{
     Scan, RecordType, FromRaw, Private, CommonPrivate,
     ScanHandler, ReadHandler, WriteHandler,
 };
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
 
 
 // Binary input
@@ -14,14 +16,27 @@ pub trait BiHandler: ScanHandler<BiRecord> + ReadHandler<BiRecord> {
     impl_into_boxed_handler!(BiHandler);
 }
 
+/// Handler trait for binary input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncBiHandler: ScanHandler<BiRecord> + AsyncReadHandler<BiRecord> {
+    impl_into_boxed_handler!(AsyncBiHandler);
+}
+
 /// Binary input private data
 pub struct BiPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn BiHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncBiHandler + Send>>,
 }
 impl BiPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(BiPrivate, CommonPrivate, base);
@@ -40,7 +55,7 @@ impl BiRecord {
     }
 }
 impl_record_private!(BiRecord, BiPrivate);
-impl_record_handler!(BiRecord, BiHandler);
+impl_record_handler!(BiRecord, BiHandler, AsyncBiHandler);
 
 impl FromRaw for BiRecord {
     type Raw = *mut biRecord;
@@ -63,14 +78,27 @@ pub trait BoHandler: ScanHandler<BoRecord> + WriteHandler<BoRecord> {
     impl_into_boxed_handler!(BoHandler);
 }
 
+/// Handler trait for binary output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncBoHandler: ScanHandler<BoRecord> + AsyncWriteHandler<BoRecord> {
+    impl_into_boxed_handler!(AsyncBoHandler);
+}
+
 /// Binary output private data
 pub struct BoPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn BoHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncBoHandler + Send>>,
 }
 impl BoPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(BoPrivate, CommonPrivate, base);
@@ -89,7 +117,7 @@ impl BoRecord {
     }
 }
 impl_record_private!(BoRecord, BoPrivate);
-impl_record_handler!(BoRecord, BoHandler);
+impl_record_handler!(BoRecord, BoHandler, AsyncBoHandler);
 
 impl FromRaw for BoRecord {
     type Raw = *mut boRecord;
@@ -0,0 +1,129 @@
+use epics_sys::{
+    calcoutRecord,
+    calcoutOOPT_calcoutOOPT_Every_Time,
+    calcoutOOPT_calcoutOOPT_On_Change,
+    calcoutOOPT_calcoutOOPT_When_Zero,
+    calcoutOOPT_calcoutOOPT_When_Non_zero,
+    calcoutOOPT_calcoutOOPT_Transition_To_Zero,
+    calcoutOOPT_calcoutOOPT_Transition_To_Non_zero,
+};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, WriteHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncWriteHandler};
+
+
+/// Number of calcout inputs (A..L)
+pub const CALCOUT_ARGS: usize = 12;
+
+/// Output execute option (OOPT field)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalcoutOopt {
+    EveryTime,
+    OnChange,
+    WhenZero,
+    WhenNonZero,
+    TransitionToZero,
+    TransitionToNonZero,
+}
+impl CalcoutOopt {
+    fn from_raw(oopt: u32) -> Option<Self> {
+        [
+            (calcoutOOPT_calcoutOOPT_Every_Time as u32, CalcoutOopt::EveryTime),
+            (calcoutOOPT_calcoutOOPT_On_Change as u32, CalcoutOopt::OnChange),
+            (calcoutOOPT_calcoutOOPT_When_Zero as u32, CalcoutOopt::WhenZero),
+            (calcoutOOPT_calcoutOOPT_When_Non_zero as u32, CalcoutOopt::WhenNonZero),
+            (calcoutOOPT_calcoutOOPT_Transition_To_Zero as u32, CalcoutOopt::TransitionToZero),
+            (calcoutOOPT_calcoutOOPT_Transition_To_Non_zero as u32, CalcoutOopt::TransitionToNonZero),
+        ].iter().find(|(raw, _)| *raw == oopt).map(|(_, o)| *o)
+    }
+}
+
+
+// Calculation output
+
+/// Handler trait for calculation output
+pub trait CalcoutHandler: ScanHandler<CalcoutRecord> + WriteHandler<CalcoutRecord> {
+    impl_into_boxed_handler!(CalcoutHandler);
+}
+
+/// Handler trait for calculation output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncCalcoutHandler: ScanHandler<CalcoutRecord> + AsyncWriteHandler<CalcoutRecord> {
+    impl_into_boxed_handler!(AsyncCalcoutHandler);
+}
+
+/// Calculation output private data
+pub struct CalcoutPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn CalcoutHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncCalcoutHandler + Send>>,
+}
+impl CalcoutPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(CalcoutPrivate, CommonPrivate, base);
+impl Private for CalcoutPrivate {}
+
+/// Calculation output record
+///
+/// Handler should write OVAL, the value computed from CALC or OCAL according to DOPT.
+pub struct CalcoutRecord {
+    raw: &'static mut calcoutRecord,
+}
+impl CalcoutRecord {
+    /// Result of CALC expression
+    pub fn val(&self) -> f64 {
+        self.raw.val
+    }
+    /// Output value
+    pub fn oval(&self) -> f64 {
+        self.raw.oval
+    }
+    pub fn set_oval(&mut self, oval: f64) {
+        self.raw.oval = oval;
+    }
+    /// Output execute option, `None` if the value is unknown
+    pub fn oopt(&self) -> Option<CalcoutOopt> {
+        CalcoutOopt::from_raw(self.raw.oopt as u32)
+    }
+
+    /// Values of inputs A..L
+    pub fn args(&self) -> [f64; CALCOUT_ARGS] {
+        [
+            self.raw.a, self.raw.b, self.raw.c, self.raw.d,
+            self.raw.e, self.raw.f, self.raw.g, self.raw.h,
+            self.raw.i, self.raw.j, self.raw.k, self.raw.l,
+        ]
+    }
+    /// Value of input `n` (A..L), `n` should be less than `CALCOUT_ARGS`
+    pub fn arg(&self, n: usize) -> f64 {
+        self.args()[n]
+    }
+}
+impl_record_private!(CalcoutRecord, CalcoutPrivate);
+impl_record_handler!(CalcoutRecord, CalcoutHandler, AsyncCalcoutHandler);
+
+impl FromRaw for CalcoutRecord {
+    type Raw = *mut calcoutRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(CalcoutRecord, Calcout);
+derive_record!(CalcoutRecord, CalcoutPrivate);
+derive_scan_record!(CalcoutRecord);
+derive_write_record!(CalcoutRecord);
+derive_deref_record!(CalcoutRecord);
+unsafe impl Send for CalcoutRecord {}
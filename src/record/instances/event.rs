@@ -0,0 +1,78 @@
+use epics_sys::{eventRecord, eventNameToHandle};
+use crate::util::{cstr_array_read_bytes, cstr_array_write_bytes};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler};
+
+
+// Event
+
+/// Handler trait for event
+pub trait EventHandler: ScanHandler<EventRecord> + ReadHandler<EventRecord> {
+    impl_into_boxed_handler!(EventHandler);
+}
+
+/// Handler trait for event performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncEventHandler: ScanHandler<EventRecord> + AsyncReadHandler<EventRecord> {
+    impl_into_boxed_handler!(AsyncEventHandler);
+}
+
+/// Event private data
+pub struct EventPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn EventHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncEventHandler + Send>>,
+}
+impl EventPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(EventPrivate, CommonPrivate, base);
+impl Private for EventPrivate {}
+
+/// Event record
+///
+/// Posts the event named by VAL each time the record is processed.
+pub struct EventRecord {
+    raw: &'static mut eventRecord,
+}
+impl EventRecord {
+    /// Name of event
+    pub fn val(&self) -> &[u8] {
+        cstr_array_read_bytes(&self.raw.val)
+    }
+    /// Set name of event to be posted
+    pub fn set_val(&mut self, val: &[u8]) {
+        if val != self.val() {
+            cstr_array_write_bytes(&mut self.raw.val, val);
+            self.raw.epvt = unsafe { eventNameToHandle(self.raw.val.as_ptr()) };
+        }
+    }
+}
+impl_record_private!(EventRecord, EventPrivate);
+impl_record_handler!(EventRecord, EventHandler, AsyncEventHandler);
+
+impl FromRaw for EventRecord {
+    type Raw = *mut eventRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(EventRecord, Event);
+derive_record!(EventRecord, EventPrivate);
+derive_scan_record!(EventRecord);
+derive_read_record!(EventRecord);
+derive_deref_record!(EventRecord);
+unsafe impl Send for EventRecord {}
@@ -0,0 +1,106 @@
+use std::slice;
+
+use epics_sys::{histogramRecord};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler};
+
+
+// Histogram
+
+/// Handler trait for histogram
+pub trait HistogramHandler: ScanHandler<HistogramRecord> + ReadHandler<HistogramRecord> {
+    impl_into_boxed_handler!(HistogramHandler);
+}
+
+/// Handler trait for histogram performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncHistogramHandler: ScanHandler<HistogramRecord> + AsyncReadHandler<HistogramRecord> {
+    impl_into_boxed_handler!(AsyncHistogramHandler);
+}
+
+/// Histogram private data
+pub struct HistogramPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn HistogramHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncHistogramHandler + Send>>,
+}
+impl HistogramPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(HistogramPrivate, CommonPrivate, base);
+impl Private for HistogramPrivate {}
+
+/// Histogram record
+///
+/// Handler should write the signal value to SGNL,
+/// the record adds it to the corresponding bucket.
+/// Handler link is taken from SVL field.
+pub struct HistogramRecord {
+    raw: &'static mut histogramRecord,
+}
+impl HistogramRecord {
+    /// Signal value
+    pub fn sgnl(&self) -> f64 {
+        self.raw.sgnl
+    }
+    pub fn set_sgnl(&mut self, sgnl: f64) {
+        self.raw.sgnl = sgnl;
+    }
+    /// Upper signal limit
+    pub fn ulim(&self) -> f64 {
+        self.raw.ulim
+    }
+    /// Lower signal limit
+    pub fn llim(&self) -> f64 {
+        self.raw.llim
+    }
+    /// Number of buckets
+    pub fn nelm(&self) -> usize {
+        if self.raw.bptr.is_null() {
+            0
+        } else {
+            self.raw.nelm as usize
+        }
+    }
+    /// Bucket counters
+    pub fn buckets(&self) -> &[u32] {
+        match self.nelm() {
+            0 => &[],
+            n => unsafe { slice::from_raw_parts(self.raw.bptr as *const u32, n) },
+        }
+    }
+    pub fn buckets_mut(&mut self) -> &mut [u32] {
+        match self.nelm() {
+            0 => &mut [],
+            n => unsafe { slice::from_raw_parts_mut(self.raw.bptr as *mut u32, n) },
+        }
+    }
+}
+impl_record_private!(HistogramRecord, HistogramPrivate);
+impl_record_handler!(HistogramRecord, HistogramHandler, AsyncHistogramHandler);
+
+impl FromRaw for HistogramRecord {
+    type Raw = *mut histogramRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(HistogramRecord, Histogram);
+derive_record!(HistogramRecord, HistogramPrivate);
+derive_scan_record!(HistogramRecord);
+derive_read_record!(HistogramRecord, svl);
+derive_deref_record!(HistogramRecord);
+unsafe impl Send for HistogramRecord {}
@@ -0,0 +1,132 @@
+use epics_sys::{int64inRecord, int64outRecord};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler, WriteHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
+
+
+// 64-bit integer input
+
+/// Handler trait for 64-bit integer input
+pub trait Int64inHandler: ScanHandler<Int64inRecord> + ReadHandler<Int64inRecord> {
+    impl_into_boxed_handler!(Int64inHandler);
+}
+
+/// Handler trait for 64-bit integer input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncInt64inHandler: ScanHandler<Int64inRecord> + AsyncReadHandler<Int64inRecord> {
+    impl_into_boxed_handler!(AsyncInt64inHandler);
+}
+
+/// 64-bit integer input private data
+pub struct Int64inPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn Int64inHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncInt64inHandler + Send>>,
+}
+impl Int64inPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(Int64inPrivate, CommonPrivate, base);
+impl Private for Int64inPrivate {}
+
+/// 64-bit integer input record
+pub struct Int64inRecord {
+    raw: &'static mut int64inRecord,
+}
+impl Int64inRecord {
+    pub fn val(&self) -> i64 {
+        self.raw.val
+    }
+    pub fn set_val(&mut self, val: i64) {
+        self.raw.val = val;
+    }
+}
+impl_record_private!(Int64inRecord, Int64inPrivate);
+impl_record_handler!(Int64inRecord, Int64inHandler, AsyncInt64inHandler);
+
+impl FromRaw for Int64inRecord {
+    type Raw = *mut int64inRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(Int64inRecord, Int64in);
+derive_record!(Int64inRecord, Int64inPrivate);
+derive_scan_record!(Int64inRecord);
+derive_read_record!(Int64inRecord);
+derive_deref_record!(Int64inRecord);
+unsafe impl Send for Int64inRecord {}
+
+
+// 64-bit integer output
+
+/// Handler trait for 64-bit integer output
+pub trait Int64outHandler: ScanHandler<Int64outRecord> + WriteHandler<Int64outRecord> {
+    impl_into_boxed_handler!(Int64outHandler);
+}
+
+/// Handler trait for 64-bit integer output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncInt64outHandler: ScanHandler<Int64outRecord> + AsyncWriteHandler<Int64outRecord> {
+    impl_into_boxed_handler!(AsyncInt64outHandler);
+}
+
+/// 64-bit integer output private data
+pub struct Int64outPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn Int64outHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncInt64outHandler + Send>>,
+}
+impl Int64outPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(Int64outPrivate, CommonPrivate, base);
+impl Private for Int64outPrivate {}
+
+/// 64-bit integer output record
+pub struct Int64outRecord {
+    raw: &'static mut int64outRecord,
+}
+impl Int64outRecord {
+    pub fn val(&self) -> i64 {
+        self.raw.val
+    }
+    pub fn set_val(&mut self, val: i64) {
+        self.raw.val = val;
+    }
+}
+impl_record_private!(Int64outRecord, Int64outPrivate);
+impl_record_handler!(Int64outRecord, Int64outHandler, AsyncInt64outHandler);
+
+impl FromRaw for Int64outRecord {
+    type Raw = *mut int64outRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(Int64outRecord, Int64out);
+derive_record!(Int64outRecord, Int64outPrivate);
+derive_scan_record!(Int64outRecord);
+derive_write_record!(Int64outRecord);
+derive_deref_record!(Int64outRecord);
+unsafe impl Send for Int64outRecord {}
@@ -5,6 +5,8 @@ use crate::record::{
     Scan, RecordType, FromRaw, Private, CommonPrivate,
     ScanHandler, ReadHandler, WriteHandler,
 };
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
 
 
 // Long input
@@ -14,14 +16,27 @@ pub trait LonginHandler: ScanHandler<LonginRecord> + ReadHandler<LonginRecord> {
     impl_into_boxed_handler!(LonginHandler);
 }
 
+/// Handler trait for long input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncLonginHandler: ScanHandler<LonginRecord> + AsyncReadHandler<LonginRecord> {
+    impl_into_boxed_handler!(AsyncLonginHandler);
+}
+
 /// Long input private data
 pub struct LonginPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn LonginHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncLonginHandler + Send>>,
 }
 impl LonginPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(LonginPrivate, CommonPrivate, base);
@@ -40,7 +55,7 @@ impl LonginRecord {
     }
 }
 impl_record_private!(LonginRecord, LonginPrivate);
-impl_record_handler!(LonginRecord, LonginHandler);
+impl_record_handler!(LonginRecord, LonginHandler, AsyncLonginHandler);
 
 impl FromRaw for LonginRecord {
     type Raw = *mut longinRecord;
@@ -63,14 +78,27 @@ pub trait LongoutHandler: ScanHandler<LongoutRecord> + WriteHandler<LongoutRecor
     impl_into_boxed_handler!(LongoutHandler);
 }
 
+/// Handler trait for long output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncLongoutHandler: ScanHandler<LongoutRecord> + AsyncWriteHandler<LongoutRecord> {
+    impl_into_boxed_handler!(AsyncLongoutHandler);
+}
+
 /// Long output private data
 pub struct LongoutPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn LongoutHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncLongoutHandler + Send>>,
 }
 impl LongoutPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(LongoutPrivate, CommonPrivate, base);
@@ -89,7 +117,7 @@ impl LongoutRecord {
     }
 }
 impl_record_private!(LongoutRecord, LongoutPrivate);
-impl_record_handler!(LongoutRecord, LongoutHandler);
+impl_record_handler!(LongoutRecord, LongoutHandler, AsyncLongoutHandler);
 
 impl FromRaw for LongoutRecord {
     type Raw = *mut longoutRecord;
@@ -0,0 +1,188 @@
+use std::slice;
+use std::str::{Utf8Error};
+
+use libc::{c_char};
+use epics_sys::{lsiRecord, lsoRecord};
+use crate::util::{cstr_array_read_bytes, cstr_array_write_bytes};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler, WriteHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
+
+
+macro_rules! impl_long_string_fields {
+    ($Record:ident) => {
+        impl $Record {
+            /// Size of value buffer including trailing zero
+            pub fn sizv(&self) -> usize {
+                if self.raw.val.is_null() {
+                    0
+                } else {
+                    self.raw.sizv as usize
+                }
+            }
+            /// Length of value including trailing zero
+            pub fn len(&self) -> usize {
+                self.raw.len as usize
+            }
+            pub fn is_empty(&self) -> bool {
+                self.val().is_empty()
+            }
+
+            fn val_buf(&self) -> &[c_char] {
+                match self.sizv() {
+                    0 => &[],
+                    n => unsafe { slice::from_raw_parts(self.raw.val as *const c_char, n) },
+                }
+            }
+            fn val_buf_mut(&mut self) -> &mut [c_char] {
+                match self.sizv() {
+                    0 => &mut [],
+                    n => unsafe { slice::from_raw_parts_mut(self.raw.val, n) },
+                }
+            }
+
+            pub fn val(&self) -> &[u8] {
+                cstr_array_read_bytes(self.val_buf())
+            }
+            pub fn val_str(&self) -> Result<&str, Utf8Error> {
+                std::str::from_utf8(self.val())
+            }
+            /// Set value, truncates it to `sizv() - 1` bytes.
+            ///
+            /// Returns number of bytes written.
+            pub fn set_val(&mut self, val: &[u8]) -> usize {
+                let n = match self.sizv() {
+                    0 => return 0,
+                    sizv => val.len().min(sizv - 1),
+                };
+                cstr_array_write_bytes(self.val_buf_mut(), &val[..n]);
+                self.raw.len = (n + 1) as _;
+                n
+            }
+            /// Set value, truncates it to `sizv() - 1` bytes at character boundary.
+            ///
+            /// Returns number of bytes written.
+            pub fn set_val_str(&mut self, val: &str) -> usize {
+                let mut n = val.len().min(self.sizv().saturating_sub(1));
+                while !val.is_char_boundary(n) {
+                    n -= 1;
+                }
+                self.set_val(val[..n].as_bytes())
+            }
+        }
+    };
+}
+
+
+// Long string input
+
+/// Handler trait for long string input
+pub trait LsiHandler: ScanHandler<LsiRecord> + ReadHandler<LsiRecord> {
+    impl_into_boxed_handler!(LsiHandler);
+}
+
+/// Handler trait for long string input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncLsiHandler: ScanHandler<LsiRecord> + AsyncReadHandler<LsiRecord> {
+    impl_into_boxed_handler!(AsyncLsiHandler);
+}
+
+/// Long string input private data
+pub struct LsiPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn LsiHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncLsiHandler + Send>>,
+}
+impl LsiPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(LsiPrivate, CommonPrivate, base);
+impl Private for LsiPrivate {}
+
+/// Long string input record
+pub struct LsiRecord {
+    raw: &'static mut lsiRecord,
+}
+impl_long_string_fields!(LsiRecord);
+impl_record_private!(LsiRecord, LsiPrivate);
+impl_record_handler!(LsiRecord, LsiHandler, AsyncLsiHandler);
+
+impl FromRaw for LsiRecord {
+    type Raw = *mut lsiRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(LsiRecord, Lsi);
+derive_record!(LsiRecord, LsiPrivate);
+derive_scan_record!(LsiRecord);
+derive_read_record!(LsiRecord);
+derive_deref_record!(LsiRecord);
+unsafe impl Send for LsiRecord {}
+
+
+// Long string output
+
+/// Handler trait for long string output
+pub trait LsoHandler: ScanHandler<LsoRecord> + WriteHandler<LsoRecord> {
+    impl_into_boxed_handler!(LsoHandler);
+}
+
+/// Handler trait for long string output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncLsoHandler: ScanHandler<LsoRecord> + AsyncWriteHandler<LsoRecord> {
+    impl_into_boxed_handler!(AsyncLsoHandler);
+}
+
+/// Long string output private data
+pub struct LsoPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn LsoHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncLsoHandler + Send>>,
+}
+impl LsoPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(LsoPrivate, CommonPrivate, base);
+impl Private for LsoPrivate {}
+
+/// Long string output record
+pub struct LsoRecord {
+    raw: &'static mut lsoRecord,
+}
+impl_long_string_fields!(LsoRecord);
+impl_record_private!(LsoRecord, LsoPrivate);
+impl_record_handler!(LsoRecord, LsoHandler, AsyncLsoHandler);
+
+impl FromRaw for LsoRecord {
+    type Raw = *mut lsoRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(LsoRecord, Lso);
+derive_record!(LsoRecord, LsoPrivate);
+derive_scan_record!(LsoRecord);
+derive_write_record!(LsoRecord);
+derive_deref_record!(LsoRecord);
+unsafe impl Send for LsoRecord {}
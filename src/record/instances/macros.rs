@@ -58,7 +58,7 @@ macro_rules! impl_record_private {
 
 #[macro_use]
 macro_rules! impl_record_handler {
-    ($Record:ident, $Handler:ident) => {
+    ($Record:ident, $Handler:ident, $AsyncHandler:ident) => {
         impl $Record {
             pub unsafe fn replace_handler(&mut self, h: Box<dyn $Handler + Send>) -> Option<Box<dyn $Handler + Send>> {
                 self.private_mut().handler.replace(h)
@@ -77,10 +77,135 @@ macro_rules! impl_record_handler {
                     None => None
                 }
             }
+
+            #[cfg(feature = "async")]
+            pub unsafe fn replace_async_handler(&mut self, h: Box<dyn $AsyncHandler + Send>) -> Option<Box<dyn $AsyncHandler + Send>> {
+                self.private_mut().async_handler.replace(h)
+            }
+            #[cfg(feature = "async")]
+            pub unsafe fn take_async_handler(&mut self) -> Option<Box<dyn $AsyncHandler + Send>> {
+                self.private_mut().async_handler.take()
+            }
+            #[cfg(feature = "async")]
+            pub unsafe fn with_async_handler<F, R>(&mut self, f: F) -> Option<R>
+            where F: FnOnce(&mut dyn $AsyncHandler, &mut Self) -> R {
+                match self.take_async_handler() {
+                    Some(mut h) => {
+                        let ret = f(h.as_mut(), self);
+                        assert!(self.replace_async_handler(h).is_none());
+                        Some(ret)
+                    },
+                    None => None
+                }
+            }
+
+            /// Record has handler of any kind
+            pub unsafe fn has_handler(&self) -> bool {
+                #[cfg(feature = "async")]
+                {
+                    if self.private().async_handler.is_some() {
+                        return true;
+                    }
+                }
+                self.private().handler.is_some()
+            }
+            /// Remove handler of any kind
+            pub unsafe fn take_any_handler(&mut self) -> Option<crate::record::AnyHandlerBox> {
+                #[cfg(feature = "async")]
+                {
+                    if let Some(h) = self.take_async_handler() {
+                        return Some(h.into());
+                    }
+                }
+                self.take_handler().map(Into::into)
+            }
         }
     }
 }
 
+/// Call `$f` with handler of any kind, handlers of both kinds have the same
+/// `Handler` and `ScanHandler` methods.
+#[macro_use]
+macro_rules! with_any_handler {
+    ($rec:expr, |$h:ident, $r:ident| $body:expr) => {{
+        let ret = $rec.with_handler(|$h, $r| $body);
+        #[cfg(feature = "async")]
+        let ret = match ret {
+            Some(ret) => Some(ret),
+            None => $rec.with_async_handler(|$h, $r| $body),
+        };
+        ret
+    }};
+}
+
+#[macro_use]
+macro_rules! impl_array_fields {
+    ($Record:ident) => {
+        impl_array_fields!($Record, nelm);
+    };
+    ($Record:ident, $size:ident) => {
+        impl $Record {
+            /// Type of array elements, `None` if the type is not supported
+            pub fn ftvl(&self) -> Option<crate::record::ArrayType> {
+                crate::record::ArrayType::from_raw(self.raw.ftvl as u32)
+            }
+            /// Maximum number of elements
+            pub fn nelm(&self) -> usize {
+                self.raw.nelm as usize
+            }
+            fn buffer_size(&self) -> usize {
+                self.raw.$size as usize
+            }
+            /// Number of valid elements in array buffer
+            pub fn nord(&self) -> usize {
+                self.raw.nord as usize
+            }
+            /// Set number of valid elements, fails if it exceeds buffer size
+            pub fn set_nord(&mut self, nord: usize) -> crate::Result<()> {
+                let size = self.buffer_size();
+                if nord > size {
+                    return Err(format!(
+                        "record({}): NORD {} exceeds buffer size {}",
+                        self.name(), nord, size,
+                    ).into());
+                }
+                self.raw.nord = nord as _;
+                Ok(())
+            }
+
+            /// View of NORD valid elements
+            pub fn array(&self) -> Option<crate::record::ArrayRef> {
+                self.ftvl().map(|t| unsafe {
+                    crate::record::ArrayRef::from_raw(t, self.raw.bptr, self.nord())
+                })
+            }
+            /// Mutable view of the whole array buffer
+            pub fn array_mut(&mut self) -> Option<crate::record::ArrayMut> {
+                let size = self.buffer_size();
+                self.ftvl().map(|t| unsafe {
+                    crate::record::ArrayMut::from_raw(t, self.raw.bptr, size)
+                })
+            }
+            /// Slice of NORD valid elements if `T` matches FTVL
+            pub fn array_as<T: crate::record::ArrayElement>(&self) -> Option<&[T]> {
+                if self.ftvl() == Some(T::atype()) && !self.raw.bptr.is_null() {
+                    Some(unsafe { crate::record::instances::array::raw_slice(self.raw.bptr, self.nord()) })
+                } else {
+                    None
+                }
+            }
+            /// Mutable slice of the whole array buffer if `T` matches FTVL
+            pub fn array_as_mut<T: crate::record::ArrayElement>(&mut self) -> Option<&mut [T]> {
+                if self.ftvl() == Some(T::atype()) && !self.raw.bptr.is_null() {
+                    Some(unsafe { crate::record::instances::array::raw_slice_mut(self.raw.bptr, self.buffer_size()) })
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
 #[macro_use]
 macro_rules! derive_record {
     ($Record:ident, $Private:ident) => {
@@ -93,10 +218,19 @@ macro_rules! derive_record {
             }
 
             unsafe fn init(&mut self) {
-                let cpvt = crate::record::raw_private_create(self.as_raw_mut(), RecordType::Ai);
+                let cpvt = crate::record::raw_private_create(
+                    self.as_raw_mut(),
+                    <$Record as crate::record::SType>::stype(),
+                );
                 let pvt = $Private::new(cpvt);
                 crate::record::raw_private_init::<$Private>(self.as_raw_mut(), pvt);
             }
+            unsafe fn handler_shutdown(&mut self) -> Option<()> {
+                with_any_handler!(self, |h, r| h.shutdown(r))
+            }
+            unsafe fn duplicate(&self) -> Self {
+                Self { raw: (&*self.raw as *const _ as *mut _).as_mut().unwrap() }
+            }
 
             unsafe fn private(&self) -> &Private {
                 self.private()
@@ -112,7 +246,10 @@ macro_rules! derive_scan_record {
     ($Record:ident) => {
         impl crate::record::ScanRecord for $Record {
             unsafe fn handler_set_scan(&mut self, scan: Scan) -> Option<crate::Result<()>> {
-                self.with_handler(|h, r| h.set_scan(r, scan))
+                with_any_handler!(self, |h, r| h.set_scan(r, scan.clone()))
+            }
+            unsafe fn handler_unset_scan(&mut self) -> Option<crate::Result<()>> {
+                with_any_handler!(self, |h, r| h.unset_scan(r))
             }
         }
     }
@@ -120,23 +257,27 @@ macro_rules! derive_scan_record {
 #[macro_use]
 macro_rules! derive_read_record {
     ($Record:ident) => {
+        derive_read_record!($Record, inp);
+    };
+    ($Record:ident, $inp:ident) => {
         impl crate::record::ReadRecord for $Record {
             fn inp(&self) -> &str {
-                let inp = &self.raw.inp;
+                let inp = &self.raw.$inp;
+                let field = stringify!($inp).to_uppercase();
                 if inp.type_ as i32 == crate::epics_sys::INST_IO as i32 {
                     unsafe { crate::util::cstr_ptr_read(inp.value.instio.string) }
                     .expect(&format!(
-                        "record({}) INP link is empty",
-                        self.name(),
+                        "record({}) {} link is empty",
+                        self.name(), field,
                     ))
                     .expect(&format!(
-                        "record({}) INP link contains bad characters",
-                        self.name(),
+                        "record({}) {} link contains bad characters",
+                        self.name(), field,
                     ))
                 } else {
                     panic!(
-                        "wrong record({}) INP link type, should be INST_IO",
-                        self.name(),
+                        "wrong record({}) {} link type, should be INST_IO",
+                        self.name(), field,
                     );
                 }
             }
@@ -147,6 +288,10 @@ macro_rules! derive_read_record {
             unsafe fn handler_read_async(&mut self) -> Option<crate::Result<()>> {
                 self.with_handler(|h, r| h.read_async(r))
             }
+            #[cfg(feature = "async")]
+            unsafe fn handler_read_future(&mut self) -> Option<crate::Result<crate::record::HandlerFuture>> {
+                self.with_async_handler(|h, r| h.read_future(crate::record::Record::duplicate(r)))
+            }
         }
         impl crate::record::Linked for $Record {
             fn link(&self) -> &str {
@@ -185,6 +330,10 @@ macro_rules! derive_write_record {
             unsafe fn handler_write_async(&mut self) -> Option<crate::Result<()>> {
                 self.with_handler(|h, r| h.write_async(r))
             }
+            #[cfg(feature = "async")]
+            unsafe fn handler_write_future(&mut self) -> Option<crate::Result<crate::record::HandlerFuture>> {
+                self.with_async_handler(|h, r| h.write_future(crate::record::Record::duplicate(r)))
+            }
         }
         impl crate::record::Linked for $Record {
             fn link(&self) -> &str {
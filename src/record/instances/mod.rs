@@ -5,8 +5,30 @@ mod analog;
 mod binary;
 mod long;
 mod string;
+mod multibit;
+mod multibit_direct;
+mod array;
+mod waveform;
+mod analog_array;
+mod long_string;
+mod int64;
+mod event;
+mod calcout;
+mod histogram;
+mod sub_array;
 
 pub use analog::*;
 pub use binary::*;
 pub use long::*;
 pub use string::*;
+pub use multibit::*;
+pub use multibit_direct::*;
+pub use array::*;
+pub use waveform::*;
+pub use analog_array::*;
+pub use long_string::*;
+pub use int64::*;
+pub use event::*;
+pub use calcout::*;
+pub use histogram::*;
+pub use sub_array::*;
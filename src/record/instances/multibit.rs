@@ -0,0 +1,220 @@
+use libc::{c_char};
+use epics_sys::{mbbiRecord, mbboRecord};
+use crate::util::{cstr_array_read_bytes, cstr_array_write_bytes};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler, WriteHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
+
+
+/// Number of states of multi-bit binary record (ZR..FF)
+pub const MBB_STATES: usize = 16;
+
+macro_rules! impl_mbb_fields {
+    ($Record:ident) => {
+        impl $Record {
+            /// Current state index
+            pub fn val(&self) -> u16 {
+                self.raw.val as u16
+            }
+            pub fn set_val(&mut self, val: u16) {
+                self.raw.val = val as _;
+            }
+
+            /// Raw value
+            pub fn rval(&self) -> u32 {
+                self.raw.rval as u32
+            }
+            pub fn set_rval(&mut self, rval: u32) {
+                self.raw.rval = rval as _;
+            }
+
+            /// Hardware mask, computed by record from NOBT and SHFT
+            pub fn mask(&self) -> u32 {
+                self.raw.mask as u32
+            }
+            pub fn set_mask(&mut self, mask: u32) {
+                self.raw.mask = mask as _;
+            }
+            /// Number of bits
+            pub fn nobt(&self) -> u16 {
+                self.raw.nobt as u16
+            }
+            /// Shift of raw value
+            pub fn shft(&self) -> u16 {
+                self.raw.shft as u16
+            }
+
+            fn state_strs(&self) -> [&[c_char]; MBB_STATES] {
+                [
+                    &self.raw.zrst, &self.raw.onst, &self.raw.twst, &self.raw.thst,
+                    &self.raw.frst, &self.raw.fvst, &self.raw.sxst, &self.raw.svst,
+                    &self.raw.eist, &self.raw.nist, &self.raw.test, &self.raw.elst,
+                    &self.raw.tvst, &self.raw.ttst, &self.raw.ftst, &self.raw.ffst,
+                ]
+            }
+            fn state_strs_mut(&mut self) -> [&mut [c_char]; MBB_STATES] {
+                [
+                    &mut self.raw.zrst, &mut self.raw.onst, &mut self.raw.twst, &mut self.raw.thst,
+                    &mut self.raw.frst, &mut self.raw.fvst, &mut self.raw.sxst, &mut self.raw.svst,
+                    &mut self.raw.eist, &mut self.raw.nist, &mut self.raw.test, &mut self.raw.elst,
+                    &mut self.raw.tvst, &mut self.raw.ttst, &mut self.raw.ftst, &mut self.raw.ffst,
+                ]
+            }
+            fn state_vals(&self) -> [u32; MBB_STATES] {
+                [
+                    self.raw.zrvl, self.raw.onvl, self.raw.twvl, self.raw.thvl,
+                    self.raw.frvl, self.raw.fvvl, self.raw.sxvl, self.raw.svvl,
+                    self.raw.eivl, self.raw.nivl, self.raw.tevl, self.raw.elvl,
+                    self.raw.tvvl, self.raw.ttvl, self.raw.ftvl, self.raw.ffvl,
+                ]
+            }
+            fn state_vals_mut(&mut self) -> [&mut u32; MBB_STATES] {
+                [
+                    &mut self.raw.zrvl, &mut self.raw.onvl, &mut self.raw.twvl, &mut self.raw.thvl,
+                    &mut self.raw.frvl, &mut self.raw.fvvl, &mut self.raw.sxvl, &mut self.raw.svvl,
+                    &mut self.raw.eivl, &mut self.raw.nivl, &mut self.raw.tevl, &mut self.raw.elvl,
+                    &mut self.raw.tvvl, &mut self.raw.ttvl, &mut self.raw.ftvl, &mut self.raw.ffvl,
+                ]
+            }
+
+            /// String of state `n` (ZRST..FFST), `n` should be less than `MBB_STATES`
+            pub fn state_str(&self, n: usize) -> &[u8] {
+                cstr_array_read_bytes(self.state_strs()[n])
+            }
+            pub fn set_state_str(&mut self, n: usize, s: &[u8]) {
+                let mut strs = self.state_strs_mut();
+                cstr_array_write_bytes(&mut strs[n], s);
+            }
+            /// Value of state `n` (ZRVL..FFVL), `n` should be less than `MBB_STATES`
+            pub fn state_val(&self, n: usize) -> u32 {
+                self.state_vals()[n]
+            }
+            pub fn set_state_val(&mut self, n: usize, v: u32) {
+                *self.state_vals_mut()[n] = v;
+            }
+            /// Index of state which value is equal to `rval`
+            pub fn find_state(&self, rval: u32) -> Option<usize> {
+                self.state_vals().iter().position(|v| *v == rval)
+            }
+        }
+    };
+}
+
+
+// Multi-bit binary input
+
+/// Handler trait for multi-bit binary input
+pub trait MbbiHandler: ScanHandler<MbbiRecord> + ReadHandler<MbbiRecord> {
+    impl_into_boxed_handler!(MbbiHandler);
+}
+
+/// Handler trait for multi-bit binary input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncMbbiHandler: ScanHandler<MbbiRecord> + AsyncReadHandler<MbbiRecord> {
+    impl_into_boxed_handler!(AsyncMbbiHandler);
+}
+
+/// Multi-bit binary input private data
+pub struct MbbiPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn MbbiHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncMbbiHandler + Send>>,
+}
+impl MbbiPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(MbbiPrivate, CommonPrivate, base);
+impl Private for MbbiPrivate {}
+
+/// Multi-bit binary input record
+///
+/// Handler should write the state index to VAL directly,
+/// RVAL is not converted by the record.
+pub struct MbbiRecord {
+    raw: &'static mut mbbiRecord,
+}
+impl_mbb_fields!(MbbiRecord);
+impl_record_private!(MbbiRecord, MbbiPrivate);
+impl_record_handler!(MbbiRecord, MbbiHandler, AsyncMbbiHandler);
+
+impl FromRaw for MbbiRecord {
+    type Raw = *mut mbbiRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(MbbiRecord, Mbbi);
+derive_record!(MbbiRecord, MbbiPrivate);
+derive_scan_record!(MbbiRecord);
+derive_read_record!(MbbiRecord);
+derive_deref_record!(MbbiRecord);
+unsafe impl Send for MbbiRecord {}
+
+
+// Multi-bit binary output
+
+/// Handler trait for multi-bit binary output
+pub trait MbboHandler: ScanHandler<MbboRecord> + WriteHandler<MbboRecord> {
+    impl_into_boxed_handler!(MbboHandler);
+}
+
+/// Handler trait for multi-bit binary output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncMbboHandler: ScanHandler<MbboRecord> + AsyncWriteHandler<MbboRecord> {
+    impl_into_boxed_handler!(AsyncMbboHandler);
+}
+
+/// Multi-bit binary output private data
+pub struct MbboPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn MbboHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncMbboHandler + Send>>,
+}
+impl MbboPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(MbboPrivate, CommonPrivate, base);
+impl Private for MbboPrivate {}
+
+/// Multi-bit binary output record
+///
+/// RVAL is computed by the record from VAL before write.
+pub struct MbboRecord {
+    raw: &'static mut mbboRecord,
+}
+impl_mbb_fields!(MbboRecord);
+impl_record_private!(MbboRecord, MbboPrivate);
+impl_record_handler!(MbboRecord, MbboHandler, AsyncMbboHandler);
+
+impl FromRaw for MbboRecord {
+    type Raw = *mut mbboRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(MbboRecord, Mbbo);
+derive_record!(MbboRecord, MbboPrivate);
+derive_scan_record!(MbboRecord);
+derive_write_record!(MbboRecord);
+derive_deref_record!(MbboRecord);
+unsafe impl Send for MbboRecord {}
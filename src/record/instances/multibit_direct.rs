@@ -0,0 +1,206 @@
+use epics_sys::{mbbiDirectRecord, mbboDirectRecord};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler, WriteHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
+
+
+/// Number of bit fields of multi-bit binary direct record (B0..B1F)
+pub const MBB_DIRECT_BITS: usize = 32;
+
+macro_rules! impl_mbb_direct_fields {
+    ($Record:ident) => {
+        impl $Record {
+            pub fn val(&self) -> u32 {
+                self.raw.val as u32
+            }
+            pub fn set_val(&mut self, val: u32) {
+                self.raw.val = val as _;
+            }
+
+            /// Raw value
+            pub fn rval(&self) -> u32 {
+                self.raw.rval as u32
+            }
+            pub fn set_rval(&mut self, rval: u32) {
+                self.raw.rval = rval as _;
+            }
+
+            /// Hardware mask, computed by record from NOBT and SHFT
+            pub fn mask(&self) -> u32 {
+                self.raw.mask as u32
+            }
+            pub fn set_mask(&mut self, mask: u32) {
+                self.raw.mask = mask as _;
+            }
+            /// Number of bits
+            pub fn nobt(&self) -> u16 {
+                self.raw.nobt as u16
+            }
+            /// Shift of raw value
+            pub fn shft(&self) -> u16 {
+                self.raw.shft as u16
+            }
+
+            fn bits(&self) -> [u8; MBB_DIRECT_BITS] {
+                [
+                    self.raw.b0, self.raw.b1, self.raw.b2, self.raw.b3,
+                    self.raw.b4, self.raw.b5, self.raw.b6, self.raw.b7,
+                    self.raw.b8, self.raw.b9, self.raw.ba, self.raw.bb,
+                    self.raw.bc, self.raw.bd, self.raw.be, self.raw.bf,
+                    self.raw.b10, self.raw.b11, self.raw.b12, self.raw.b13,
+                    self.raw.b14, self.raw.b15, self.raw.b16, self.raw.b17,
+                    self.raw.b18, self.raw.b19, self.raw.b1a, self.raw.b1b,
+                    self.raw.b1c, self.raw.b1d, self.raw.b1e, self.raw.b1f,
+                ]
+            }
+            fn bits_mut(&mut self) -> [&mut u8; MBB_DIRECT_BITS] {
+                [
+                    &mut self.raw.b0, &mut self.raw.b1, &mut self.raw.b2, &mut self.raw.b3,
+                    &mut self.raw.b4, &mut self.raw.b5, &mut self.raw.b6, &mut self.raw.b7,
+                    &mut self.raw.b8, &mut self.raw.b9, &mut self.raw.ba, &mut self.raw.bb,
+                    &mut self.raw.bc, &mut self.raw.bd, &mut self.raw.be, &mut self.raw.bf,
+                    &mut self.raw.b10, &mut self.raw.b11, &mut self.raw.b12, &mut self.raw.b13,
+                    &mut self.raw.b14, &mut self.raw.b15, &mut self.raw.b16, &mut self.raw.b17,
+                    &mut self.raw.b18, &mut self.raw.b19, &mut self.raw.b1a, &mut self.raw.b1b,
+                    &mut self.raw.b1c, &mut self.raw.b1d, &mut self.raw.b1e, &mut self.raw.b1f,
+                ]
+            }
+
+            /// Bit field `n` (B0..B1F), `n` should be less than `MBB_DIRECT_BITS`
+            pub fn bit(&self, n: usize) -> bool {
+                self.bits()[n] != 0
+            }
+            pub fn set_bit(&mut self, n: usize, b: bool) {
+                *self.bits_mut()[n] = b as u8;
+            }
+            /// Bit fields packed into a word, B0 is the least significant bit
+            pub fn bits_word(&self) -> u32 {
+                self.bits().iter().enumerate()
+                .fold(0, |w, (i, b)| w | (((*b != 0) as u32) << i))
+            }
+            pub fn set_bits_word(&mut self, w: u32) {
+                for (i, b) in self.bits_mut().iter_mut().enumerate() {
+                    **b = ((w >> i) & 1) as u8;
+                }
+            }
+        }
+    };
+}
+
+
+// Multi-bit binary input direct
+
+/// Handler trait for multi-bit binary input direct
+pub trait MbbiDirectHandler: ScanHandler<MbbiDirectRecord> + ReadHandler<MbbiDirectRecord> {
+    impl_into_boxed_handler!(MbbiDirectHandler);
+}
+
+/// Handler trait for multi-bit binary input direct performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncMbbiDirectHandler: ScanHandler<MbbiDirectRecord> + AsyncReadHandler<MbbiDirectRecord> {
+    impl_into_boxed_handler!(AsyncMbbiDirectHandler);
+}
+
+/// Multi-bit binary input direct private data
+pub struct MbbiDirectPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn MbbiDirectHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncMbbiDirectHandler + Send>>,
+}
+impl MbbiDirectPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(MbbiDirectPrivate, CommonPrivate, base);
+impl Private for MbbiDirectPrivate {}
+
+/// Multi-bit binary input direct record
+///
+/// Handler should write VAL directly, bit fields are updated by the record from VAL.
+pub struct MbbiDirectRecord {
+    raw: &'static mut mbbiDirectRecord,
+}
+impl_mbb_direct_fields!(MbbiDirectRecord);
+impl_record_private!(MbbiDirectRecord, MbbiDirectPrivate);
+impl_record_handler!(MbbiDirectRecord, MbbiDirectHandler, AsyncMbbiDirectHandler);
+
+impl FromRaw for MbbiDirectRecord {
+    type Raw = *mut mbbiDirectRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(MbbiDirectRecord, MbbiDirect);
+derive_record!(MbbiDirectRecord, MbbiDirectPrivate);
+derive_scan_record!(MbbiDirectRecord);
+derive_read_record!(MbbiDirectRecord);
+derive_deref_record!(MbbiDirectRecord);
+unsafe impl Send for MbbiDirectRecord {}
+
+
+// Multi-bit binary output direct
+
+/// Handler trait for multi-bit binary output direct
+pub trait MbboDirectHandler: ScanHandler<MbboDirectRecord> + WriteHandler<MbboDirectRecord> {
+    impl_into_boxed_handler!(MbboDirectHandler);
+}
+
+/// Handler trait for multi-bit binary output direct performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncMbboDirectHandler: ScanHandler<MbboDirectRecord> + AsyncWriteHandler<MbboDirectRecord> {
+    impl_into_boxed_handler!(AsyncMbboDirectHandler);
+}
+
+/// Multi-bit binary output direct private data
+pub struct MbboDirectPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn MbboDirectHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncMbboDirectHandler + Send>>,
+}
+impl MbboDirectPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(MbboDirectPrivate, CommonPrivate, base);
+impl Private for MbboDirectPrivate {}
+
+/// Multi-bit binary output direct record
+///
+/// VAL is assembled by the record from bit fields and RVAL is computed from VAL before write.
+pub struct MbboDirectRecord {
+    raw: &'static mut mbboDirectRecord,
+}
+impl_mbb_direct_fields!(MbboDirectRecord);
+impl_record_private!(MbboDirectRecord, MbboDirectPrivate);
+impl_record_handler!(MbboDirectRecord, MbboDirectHandler, AsyncMbboDirectHandler);
+
+impl FromRaw for MbboDirectRecord {
+    type Raw = *mut mbboDirectRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(MbboDirectRecord, MbboDirect);
+derive_record!(MbboDirectRecord, MbboDirectPrivate);
+derive_scan_record!(MbboDirectRecord);
+derive_write_record!(MbboDirectRecord);
+derive_deref_record!(MbboDirectRecord);
+unsafe impl Send for MbboDirectRecord {}
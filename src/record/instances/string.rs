@@ -5,6 +5,8 @@ use crate::record::{
     Scan, RecordType, FromRaw, Private, CommonPrivate,
     ScanHandler, ReadHandler, WriteHandler,
 };
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler, AsyncWriteHandler};
 
 
 // String input
@@ -14,14 +16,27 @@ pub trait StringinHandler: ScanHandler<StringinRecord> + ReadHandler<StringinRec
     impl_into_boxed_handler!(StringinHandler);
 }
 
+/// Handler trait for string input performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncStringinHandler: ScanHandler<StringinRecord> + AsyncReadHandler<StringinRecord> {
+    impl_into_boxed_handler!(AsyncStringinHandler);
+}
+
 /// String input private data
 pub struct StringinPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn StringinHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncStringinHandler + Send>>,
 }
 impl StringinPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(StringinPrivate, CommonPrivate, base);
@@ -40,7 +55,7 @@ impl StringinRecord {
     }
 }
 impl_record_private!(StringinRecord, StringinPrivate);
-impl_record_handler!(StringinRecord, StringinHandler);
+impl_record_handler!(StringinRecord, StringinHandler, AsyncStringinHandler);
 
 impl FromRaw for StringinRecord {
     type Raw = *mut stringinRecord;
@@ -63,14 +78,27 @@ pub trait StringoutHandler: ScanHandler<StringoutRecord> + WriteHandler<Stringou
     impl_into_boxed_handler!(StringoutHandler);
 }
 
+/// Handler trait for string output performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncStringoutHandler: ScanHandler<StringoutRecord> + AsyncWriteHandler<StringoutRecord> {
+    impl_into_boxed_handler!(AsyncStringoutHandler);
+}
+
 /// String output private data
 pub struct StringoutPrivate {
     base: CommonPrivate,
     handler: Option<Box<dyn StringoutHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncStringoutHandler + Send>>,
 }
 impl StringoutPrivate {
     fn new(cpvt: CommonPrivate) -> Self {
-        Self { base: cpvt, handler: None }
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
     }
 }
 derive_deref!(StringoutPrivate, CommonPrivate, base);
@@ -89,7 +117,7 @@ impl StringoutRecord {
     }
 }
 impl_record_private!(StringoutRecord, StringoutPrivate);
-impl_record_handler!(StringoutRecord, StringoutHandler);
+impl_record_handler!(StringoutRecord, StringoutHandler, AsyncStringoutHandler);
 
 impl FromRaw for StringoutRecord {
     type Raw = *mut stringoutRecord;
@@ -0,0 +1,77 @@
+use epics_sys::{subArrayRecord};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler};
+
+
+// Sub-array
+
+/// Handler trait for sub-array
+pub trait SubArrayHandler: ScanHandler<SubArrayRecord> + ReadHandler<SubArrayRecord> {
+    impl_into_boxed_handler!(SubArrayHandler);
+}
+
+/// Handler trait for sub-array performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncSubArrayHandler: ScanHandler<SubArrayRecord> + AsyncReadHandler<SubArrayRecord> {
+    impl_into_boxed_handler!(AsyncSubArrayHandler);
+}
+
+/// Sub-array private data
+pub struct SubArrayPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn SubArrayHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncSubArrayHandler + Send>>,
+}
+impl SubArrayPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(SubArrayPrivate, CommonPrivate, base);
+impl Private for SubArrayPrivate {}
+
+/// Sub-array record
+///
+/// Array buffer has MALM elements.
+/// Handler should place up to NELM elements starting from INDX
+/// of the source array at the beginning of the buffer and set NORD.
+pub struct SubArrayRecord {
+    raw: &'static mut subArrayRecord,
+}
+impl SubArrayRecord {
+    /// Maximum number of elements of source array
+    pub fn malm(&self) -> usize {
+        self.raw.malm as usize
+    }
+    /// Index of the first element of sub-array in source array
+    pub fn indx(&self) -> usize {
+        self.raw.indx as usize
+    }
+}
+impl_array_fields!(SubArrayRecord, malm);
+impl_record_private!(SubArrayRecord, SubArrayPrivate);
+impl_record_handler!(SubArrayRecord, SubArrayHandler, AsyncSubArrayHandler);
+
+impl FromRaw for SubArrayRecord {
+    type Raw = *mut subArrayRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(SubArrayRecord, SubArray);
+derive_record!(SubArrayRecord, SubArrayPrivate);
+derive_scan_record!(SubArrayRecord);
+derive_read_record!(SubArrayRecord);
+derive_deref_record!(SubArrayRecord);
+unsafe impl Send for SubArrayRecord {}
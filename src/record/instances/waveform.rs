@@ -0,0 +1,65 @@
+use epics_sys::{waveformRecord};
+
+use crate::record::{
+    Scan, RecordType, FromRaw, Private, CommonPrivate,
+    ScanHandler, ReadHandler,
+};
+#[cfg(feature = "async")]
+use crate::record::{AsyncReadHandler};
+
+
+// Waveform
+
+/// Handler trait for waveform
+pub trait WaveformHandler: ScanHandler<WaveformRecord> + ReadHandler<WaveformRecord> {
+    impl_into_boxed_handler!(WaveformHandler);
+}
+
+/// Handler trait for waveform performing requests by futures
+#[cfg(feature = "async")]
+pub trait AsyncWaveformHandler: ScanHandler<WaveformRecord> + AsyncReadHandler<WaveformRecord> {
+    impl_into_boxed_handler!(AsyncWaveformHandler);
+}
+
+/// Waveform private data
+pub struct WaveformPrivate {
+    base: CommonPrivate,
+    handler: Option<Box<dyn WaveformHandler + Send>>,
+    #[cfg(feature = "async")]
+    async_handler: Option<Box<dyn AsyncWaveformHandler + Send>>,
+}
+impl WaveformPrivate {
+    fn new(cpvt: CommonPrivate) -> Self {
+        Self {
+            base: cpvt,
+            handler: None,
+            #[cfg(feature = "async")]
+            async_handler: None,
+        }
+    }
+}
+derive_deref!(WaveformPrivate, CommonPrivate, base);
+impl Private for WaveformPrivate {}
+
+/// Waveform record
+///
+/// Handler should fill the buffer and set NORD to the number of elements read.
+pub struct WaveformRecord {
+    raw: &'static mut waveformRecord,
+}
+impl_array_fields!(WaveformRecord);
+impl_record_private!(WaveformRecord, WaveformPrivate);
+impl_record_handler!(WaveformRecord, WaveformHandler, AsyncWaveformHandler);
+
+impl FromRaw for WaveformRecord {
+    type Raw = *mut waveformRecord;
+    unsafe fn from_raw(raw: Self::Raw) -> Self {
+        Self { raw: raw.as_mut().unwrap() }
+    }
+}
+derive_stype!(WaveformRecord, Waveform);
+derive_record!(WaveformRecord, WaveformPrivate);
+derive_scan_record!(WaveformRecord);
+derive_read_record!(WaveformRecord);
+derive_deref_record!(WaveformRecord);
+unsafe impl Send for WaveformRecord {}
@@ -1,4 +1,5 @@
 mod common;
+mod alarm;
 mod handler;
 mod request;
 
@@ -7,6 +8,7 @@ mod any;
 
 
 pub use common::*;
+pub use alarm::*;
 pub use handler::*;
 pub use request::*;
 
@@ -16,6 +18,6 @@ pub use any::*;
 pub mod prelude {
 	pub use super::common::{
 		Linked, Record, ScanRecord,
-		ReadRecord, WriteRecord,
+		ReadRecord, WriteRecord, LinconvRecord,
 	};
 }
@@ -11,10 +11,20 @@ use crate::{
 
 macro_rules! impl_handler {
     ($Handler:ident, $opt:ident, $Record:ident) => {
-        impl Handler<$Record> for $Handler {}
+        impl Handler<$Record> for $Handler {
+            fn shutdown(&mut self, record: &mut $Record) {
+                info!("{}.shutdown({})", stringify!($Record), record.name());
+            }
+        }
         impl InitHandler<$Record> for $Handler {
             fn init(record: &mut $Record, args: &[&str]) -> epics::Result<Self> {
                 info!("record_init({}, {:?})", record.name(), args);
+                if args.contains(&"queue") {
+                    record.bind_queue("test")?;
+                }
+                if args.contains(&"timeout") {
+                    record.set_async_timeout(Some(std::time::Duration::from_secs(1)));
+                }
                 Ok(Self {})
             }
         }
@@ -62,6 +72,42 @@ macro_rules! impl_write_handler {
     };
 }
 
+#[cfg(feature = "async")]
+macro_rules! impl_read_future_handler {
+    ($Handler:ident, $Record:ident) => {
+        impl AsyncReadHandler<$Record> for $Handler {
+            fn read_future(&mut self, record: $Record) -> epics::Result<HandlerFuture> {
+                info!("{}.read_future({})", stringify!($Record), record.name());
+                Ok(Box::pin(async move {
+                    if record.cancel_token().is_cancelled() {
+                        return Err("cancelled".into());
+                    }
+                    info!("{}.read_future({}): done", stringify!($Record), record.name());
+                    Ok(())
+                }))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "async")]
+macro_rules! impl_write_future_handler {
+    ($Handler:ident, $Record:ident) => {
+        impl AsyncWriteHandler<$Record> for $Handler {
+            fn write_future(&mut self, record: $Record) -> epics::Result<HandlerFuture> {
+                info!("{}.write_future({})", stringify!($Record), record.name());
+                Ok(Box::pin(async move {
+                    if record.cancel_token().is_cancelled() {
+                        return Err("cancelled".into());
+                    }
+                    info!("{}.write_future({}): done", stringify!($Record), record.name());
+                    Ok(())
+                }))
+            }
+        }
+    };
+}
+
 struct AiTest {}
 impl_handler!(AiTest, Ai, AiRecord);
 impl_scan_handler!(AiTest, AiRecord);
@@ -110,14 +156,173 @@ impl_scan_handler!(StringoutTest, StringoutRecord);
 impl_write_handler!(StringoutTest, StringoutRecord);
 impl StringoutHandler for StringoutTest {}
 
+struct MbbiTest {}
+impl_handler!(MbbiTest, Mbbi, MbbiRecord);
+impl_scan_handler!(MbbiTest, MbbiRecord);
+impl_read_handler!(MbbiTest, MbbiRecord);
+impl MbbiHandler for MbbiTest {}
+
+struct MbboTest {}
+impl_handler!(MbboTest, Mbbo, MbboRecord);
+impl_scan_handler!(MbboTest, MbboRecord);
+impl_write_handler!(MbboTest, MbboRecord);
+impl MbboHandler for MbboTest {}
+
+struct MbbiDirectTest {}
+impl_handler!(MbbiDirectTest, MbbiDirect, MbbiDirectRecord);
+impl_scan_handler!(MbbiDirectTest, MbbiDirectRecord);
+impl_read_handler!(MbbiDirectTest, MbbiDirectRecord);
+impl MbbiDirectHandler for MbbiDirectTest {}
+
+struct MbboDirectTest {}
+impl_handler!(MbboDirectTest, MbboDirect, MbboDirectRecord);
+impl_scan_handler!(MbboDirectTest, MbboDirectRecord);
+impl_write_handler!(MbboDirectTest, MbboDirectRecord);
+impl MbboDirectHandler for MbboDirectTest {}
+
+struct WaveformTest {}
+impl_handler!(WaveformTest, Waveform, WaveformRecord);
+impl_scan_handler!(WaveformTest, WaveformRecord);
+impl_read_handler!(WaveformTest, WaveformRecord);
+impl WaveformHandler for WaveformTest {}
+
+struct AaiTest {}
+impl_handler!(AaiTest, Aai, AaiRecord);
+impl_scan_handler!(AaiTest, AaiRecord);
+impl_read_handler!(AaiTest, AaiRecord);
+impl AaiHandler for AaiTest {}
+
+struct AaoTest {}
+impl_handler!(AaoTest, Aao, AaoRecord);
+impl_scan_handler!(AaoTest, AaoRecord);
+impl_write_handler!(AaoTest, AaoRecord);
+impl AaoHandler for AaoTest {}
+
+struct LsiTest {}
+impl_handler!(LsiTest, Lsi, LsiRecord);
+impl_scan_handler!(LsiTest, LsiRecord);
+impl_read_handler!(LsiTest, LsiRecord);
+impl LsiHandler for LsiTest {}
+
+struct LsoTest {}
+impl_handler!(LsoTest, Lso, LsoRecord);
+impl_scan_handler!(LsoTest, LsoRecord);
+impl_write_handler!(LsoTest, LsoRecord);
+impl LsoHandler for LsoTest {}
+
+struct Int64inTest {}
+impl_handler!(Int64inTest, Int64in, Int64inRecord);
+impl_scan_handler!(Int64inTest, Int64inRecord);
+impl_read_handler!(Int64inTest, Int64inRecord);
+impl Int64inHandler for Int64inTest {}
+
+struct Int64outTest {}
+impl_handler!(Int64outTest, Int64out, Int64outRecord);
+impl_scan_handler!(Int64outTest, Int64outRecord);
+impl_write_handler!(Int64outTest, Int64outRecord);
+impl Int64outHandler for Int64outTest {}
+
+// Future handlers are only type checked, device support binds sync ones
+#[cfg(feature = "async")]
+struct Int64inFutureTest {}
+#[cfg(feature = "async")]
+impl_handler!(Int64inFutureTest, Int64in, Int64inRecord);
+#[cfg(feature = "async")]
+impl_scan_handler!(Int64inFutureTest, Int64inRecord);
+#[cfg(feature = "async")]
+impl_read_future_handler!(Int64inFutureTest, Int64inRecord);
+#[cfg(feature = "async")]
+impl AsyncInt64inHandler for Int64inFutureTest {}
+
+#[cfg(feature = "async")]
+struct Int64outFutureTest {}
+#[cfg(feature = "async")]
+impl_handler!(Int64outFutureTest, Int64out, Int64outRecord);
+#[cfg(feature = "async")]
+impl_scan_handler!(Int64outFutureTest, Int64outRecord);
+#[cfg(feature = "async")]
+impl_write_future_handler!(Int64outFutureTest, Int64outRecord);
+#[cfg(feature = "async")]
+impl AsyncInt64outHandler for Int64outFutureTest {}
+
+struct EventTest {}
+impl_handler!(EventTest, Event, EventRecord);
+impl_scan_handler!(EventTest, EventRecord);
+impl_read_handler!(EventTest, EventRecord);
+impl EventHandler for EventTest {}
+
+struct CalcoutTest {}
+impl_handler!(CalcoutTest, Calcout, CalcoutRecord);
+impl_scan_handler!(CalcoutTest, CalcoutRecord);
+impl_write_handler!(CalcoutTest, CalcoutRecord);
+impl CalcoutHandler for CalcoutTest {}
+
+struct HistogramTest {}
+impl_handler!(HistogramTest, Histogram, HistogramRecord);
+impl_scan_handler!(HistogramTest, HistogramRecord);
+impl_read_handler!(HistogramTest, HistogramRecord);
+impl HistogramHandler for HistogramTest {}
+
+struct SubArrayTest {}
+impl_handler!(SubArrayTest, SubArray, SubArrayRecord);
+impl_scan_handler!(SubArrayTest, SubArrayRecord);
+impl_read_handler!(SubArrayTest, SubArrayRecord);
+impl SubArrayHandler for SubArrayTest {}
+
+
+/// Test command registered by attribute.
+#[epics::iocsh_command(name = "testAttrCommand")]
+fn test_attr_command(a: &str, #[default(1.0)] b: f64) -> epics::Result<()> {
+    info!("testAttrCommand({}, {})", a, b);
+    Ok(())
+}
+
+/// Test command with defaults of every kind.
+///
+/// Takes optional arguments.
+#[epics::iocsh_command(name = "testAttrDefaults")]
+fn test_attr_defaults(
+    #[default(2)] a: i32,
+    #[default(true)] b: bool,
+    #[default("none")] c: String,
+    d: Option<&str>,
+) -> epics::Result<()> {
+    info!("testAttrDefaults({}, {}, {}, {:?})", a, b, c, d);
+    Ok(())
+}
+
+#[epics::iocsh_command]
+fn test_attr_plain(a: i32, args: &[&str]) {
+    info!("test_attr_plain({}, {:?})", a, args);
+}
 
 fn init(context: &mut Context) -> epics::Result<()> {
     simple_logger::init().unwrap();
     info!("init");
+    context.set_read_error_alarm(Some((AlarmStatus::Comm, AlarmSeverity::Invalid)));
+    context.set_async_threads(epics::async_proc::Priority::High, 2)?;
+    context.create_queue("test")?;
+    context.set_abort_on_command_error(true);
+    let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    epics::command::FuncDef::new("test_closure_command")
+    .arg("name", epics::command::ArgType::String)
+    .register_fn(Box::new(move |args| {
+        let n = calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        info!("test_closure_command({}): call {}", args.get::<&str>(0)?, n);
+        Ok(())
+    }))?;
     register_command!(context, fn test_command(a: i32, b: f64, c: &str) -> epics::Result<()> {
         info!("test_command({}, {}, {})", a, b, c);
         Ok(())
     });
+    register_command!(context, fn test_command_opt(a: Option<i32>, b: bool, c: Vec<String>) -> epics::Result<()> {
+        info!("test_command_opt({:?}, {}, {:?})", a, b, c);
+        Ok(())
+    });
+    register_command!(context, fn test_command_argv(a: Option<bool>, c: &[&str]) -> epics::Result<()> {
+        info!("test_command_argv({:?}, {:?})", a, c);
+        Ok(())
+    });
     Ok(())
 }
 
@@ -132,5 +337,88 @@ bind_device_support!(
         LongoutTest,
         StringinTest,
         StringoutTest,
+        MbbiTest,
+        MbboTest,
+        MbbiDirectTest,
+        MbboDirectTest,
+        WaveformTest,
+        AaiTest,
+        AaoTest,
+        LsiTest,
+        LsoTest,
+        Int64inTest,
+        Int64outTest,
+        EventTest,
+        CalcoutTest,
+        HistogramTest,
+        SubArrayTest,
     },
 );
+
+fn test_records_info() -> Vec<epics::device_support::RecordInfo> {
+    use epics::device_support::RecordInfo;
+    vec![
+        RecordInfo {
+            name: "TEST:AI".into(),
+            rtype: RecordType::Ai,
+            handler: "AiTest".into(),
+            args: vec!["queue".into(), "timeout".into()],
+            queue: Some("test".into()),
+            failure: None,
+        },
+        RecordInfo {
+            name: "TEST:AO".into(),
+            rtype: RecordType::Ao,
+            handler: "AoTest".into(),
+            args: Vec::new(),
+            queue: None,
+            failure: Some("record_init: panic: test".into()),
+        },
+    ]
+}
+
+#[test]
+fn test_rsbind_records() {
+    use epics::device_support::records_report;
+    let records = test_records_info();
+    assert_eq!(records_report(&records, 0), vec![
+        "Device Support: rsbind, records: 2",
+        "    TEST:AI Ai handler: AiTest",
+        "    TEST:AO Ao handler: AoTest",
+    ]);
+    assert_eq!(records_report(&records, 1), vec![
+        "Device Support: rsbind, records: 2",
+        "    TEST:AI Ai handler: AiTest",
+        "        args: queue, timeout",
+        "        queue: test",
+        "    TEST:AO Ao handler: AoTest",
+        "        args: ",
+        "        disabled: record_init: panic: test",
+    ]);
+}
+
+#[test]
+fn test_rsbind_queues() {
+    use epics::{device_support::queues_report, async_proc::QueueInfo};
+    let queues = vec![QueueInfo {
+        name: "queue-test".into(),
+        threads: 1,
+        pending: 2,
+        active: 1,
+    }];
+    assert_eq!(queues_report(&queues, Some(3)), vec![
+        "Device Support: rsbind, queues: 1",
+        "    queue-test threads: 1 pending: 2 active: 1",
+        "    futures: 3",
+    ]);
+}
+
+#[test]
+fn test_rsbind_status() {
+    use epics::device_support::status_report;
+    assert_eq!(status_report(false, &test_records_info()), vec![
+        "Device Support: rsbind, gate: closed",
+        "    records: 2 disabled: 1",
+        "    TEST:AO disabled: record_init: panic: test",
+    ]);
+}
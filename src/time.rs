@@ -0,0 +1,67 @@
+use std::time::{SystemTime, Duration, UNIX_EPOCH};
+
+use epics_sys::{epicsTimeStamp};
+
+
+/// Seconds between POSIX epoch (1970-01-01) and EPICS epoch (1990-01-01)
+pub const POSIX_TIME_AT_EPICS_EPOCH: u64 = 631_152_000;
+
+/// EPICS epoch as system time
+pub fn epics_epoch() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(POSIX_TIME_AT_EPICS_EPOCH)
+}
+
+/// Convert system time to EPICS time stamp.
+///
+/// Returns `None` if time cannot be represented by EPICS time stamp,
+/// i.e. it is before EPICS epoch or too far in the future.
+pub fn to_epics(time: SystemTime) -> Option<epicsTimeStamp> {
+    let dur = time.duration_since(epics_epoch()).ok()?;
+    if dur.as_secs() > u32::max_value() as u64 {
+        return None;
+    }
+    Some(epicsTimeStamp {
+        secPastEpoch: dur.as_secs() as _,
+        nsec: dur.subsec_nanos() as _,
+    })
+}
+
+/// Convert EPICS time stamp to system time
+pub fn from_epics(ts: &epicsTimeStamp) -> SystemTime {
+    epics_epoch() + Duration::new(ts.secPastEpoch as u64, ts.nsec as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_epoch() {
+        assert!(to_epics(epics_epoch() - Duration::from_nanos(1)).is_none());
+        assert!(to_epics(UNIX_EPOCH).is_none());
+    }
+
+    #[test]
+    fn out_of_range() {
+        let max = epics_epoch() + Duration::from_secs(u32::max_value() as u64);
+        assert_eq!(to_epics(max).unwrap().secPastEpoch, u32::max_value() as _);
+        assert!(to_epics(max + Duration::from_secs(1)).is_none());
+    }
+
+    #[test]
+    fn epoch() {
+        let ts = to_epics(epics_epoch()).unwrap();
+        assert_eq!(ts.secPastEpoch, 0);
+        assert_eq!(ts.nsec, 0);
+        assert_eq!(from_epics(&ts), epics_epoch());
+    }
+
+    #[test]
+    fn round_trip() {
+        let time = UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789);
+        let ts = to_epics(time).unwrap();
+        assert_eq!(ts.secPastEpoch as u64, 1_600_000_000 - POSIX_TIME_AT_EPICS_EPOCH);
+        assert_eq!(ts.nsec, 123_456_789);
+        assert_eq!(from_epics(&ts), time);
+    }
+}